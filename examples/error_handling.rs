@@ -1,12 +1,13 @@
-//! Example demonstrating error handling and retry logic
+//! Example demonstrating error handling and automatic retries
 
 use docaroo_rs::{
     DocarooClient, 
     DocarooError,
-    models::PricingRequest
+    client::DocarooConfig,
+    models::PricingRequest,
+    retry::{RetryAttempt, RetryPolicy},
 };
-use std::{env, time::Duration};
-use tokio::time::sleep;
+use std::{env, sync::Arc, time::Duration};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -59,8 +60,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     println!("Authentication failed: {}", msg);
                     println!("Action: Check your API key");
                 }
-                DocarooError::ApiError { code, message, request_id } => {
-                    println!("API error ({}): {}", code, message);
+                DocarooError::ApiError { status, code, message, request_id } => {
+                    println!("API error ({}, HTTP {}): {}", code, status, message);
                     if let Some(id) = request_id {
                         println!("Request ID for support: {}", id);
                     }
@@ -70,58 +71,43 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
-    // Example 3: Retry logic for transient errors
-    println!("\n\nExample 3: Retry logic for transient errors");
-    println!("------------------------------------------");
-    
+    // Example 3: Automatic retries for transient errors
+    println!("\n\nExample 3: Automatic retries for transient errors");
+    println!("------------------------------------------------");
+
+    // Retryable errors (rate limits, timeouts, 5xx, connection failures) are retried
+    // by the client itself: rate limits wait for the server's retry_after, everything
+    // else backs off exponentially with jitter.
+    let retrying_client = DocarooClient::with_config(
+        DocarooConfig::builder()
+            .api_key(env::var("DOCAROO_API_KEY").unwrap_or_else(|_| "demo-key-for-testing".to_string()))
+            .retry_policy(
+                RetryPolicy::builder()
+                    .max_retries(3)
+                    .base_delay(Duration::from_millis(500))
+                    .max_delay(Duration::from_secs(10))
+                    .jitter(true)
+                    .on_retry(Arc::new(|attempt: RetryAttempt| {
+                        println!(
+                            "Retry {} for {} in {:?}: {}",
+                            attempt.attempt, attempt.endpoint, attempt.delay, attempt.error
+                        );
+                    }))
+                    .build(),
+            )
+            .build(),
+    );
+
     let request = PricingRequest::builder()
         .npis(vec!["1043566623".to_string()])
         .condition_code("99214")
         .build();
 
-    async fn call_with_retry(
-        client: &DocarooClient,
-        request: PricingRequest,
-        max_retries: u32,
-    ) -> Result<(), DocarooError> {
-        let mut retries = 0;
-        
-        loop {
-            match client.pricing().get_in_network_rates(request.clone()).await {
-                Ok(response) => {
-                    println!("Success! Found {} NPIs with data", response.data.len());
-                    return Ok(());
-                }
-                Err(e) => {
-                    if e.is_retryable() && retries < max_retries {
-                        retries += 1;
-                        
-                        let delay = match &e {
-                            DocarooError::RateLimitExceeded { retry_after } => {
-                                println!("Rate limit hit. Waiting {} seconds...", retry_after);
-                                Duration::from_secs(*retry_after)
-                            }
-                            _ => {
-                                let backoff = Duration::from_secs(2u64.pow(retries));
-                                println!("Retryable error: {}. Retry {} of {} in {:?}...", 
-                                    e, retries, max_retries, backoff);
-                                backoff
-                            }
-                        };
-                        
-                        sleep(delay).await;
-                        continue;
-                    } else {
-                        println!("Non-retryable error or max retries reached: {}", e);
-                        return Err(e);
-                    }
-                }
-            }
-        }
+    match retrying_client.pricing().get_in_network_rates(request).await {
+        Ok(response) => println!("Success! Found {} NPIs with data", response.data.len()),
+        Err(e) => println!("Non-retryable error or max retries reached: {}", e),
     }
 
-    let _ = call_with_retry(&client, request, 3).await;
-
     // Example 4: Comprehensive error information
     println!("\n\nExample 4: Comprehensive error information");
     println!("-----------------------------------------");
@@ -1,6 +1,11 @@
 //! Example demonstrating how to use the procedure likelihood API
 
-use docaroo_rs::{DocarooClient, models::LikelihoodRequest};
+use docaroo_rs::{
+    DocarooClient,
+    analysis::ProviderProfileBuilder,
+    models::{CodeType, LikelihoodRequest},
+};
+use futures::StreamExt;
 use std::env;
 
 #[tokio::main]
@@ -19,7 +24,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let request = LikelihoodRequest::builder()
         .npis(vec!["1487648176".to_string()])
         .condition_code("99214")
-        .code_type("CPT")
+        .code_type(CodeType::Cpt)
         .build();
 
     match client.procedures().get_likelihood(request).await {
@@ -34,14 +39,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 println!("  Likelihood: {:.1}%", data.likelihood * 100.0);
                 
                 // Interpret the score
-                let interpretation = match data.likelihood {
-                    x if x >= 0.8 => "Highly likely to perform this procedure",
-                    x if x >= 0.6 => "Likely to perform this procedure",
-                    x if x >= 0.4 => "Moderately likely to perform this procedure",
-                    x if x >= 0.2 => "Unlikely to perform this procedure",
-                    _ => "Very unlikely to perform this procedure",
-                };
-                println!("  Interpretation: {}", interpretation);
+                println!(
+                    "  Interpretation: {} to perform this procedure",
+                    data.confidence_band()
+                );
             }
         }
         Err(e) => {
@@ -59,18 +60,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let npis = vec!["1487648176", "1043566623", "1972767655"];
     
     match client.procedures()
-        .check_providers(&npis, "99214", "CPT")
+        .check_providers(&npis, "99214", CodeType::Cpt)
         .await 
     {
         Ok(response) => {
             println!("Checking {} providers for procedure 99214", npis.len());
             
-            // Sort by likelihood score
-            let mut results: Vec<_> = response.data.iter().collect();
-            results.sort_by(|a, b| b.1.likelihood.partial_cmp(&a.1.likelihood).unwrap());
-            
             println!("\nRanked by likelihood:");
-            for (i, (npi, data)) in results.iter().enumerate() {
+            for (i, (npi, data)) in response.ranked().iter().enumerate() {
                 println!("{}. NPI {}: {:.1}%", 
                     i + 1, npi, data.likelihood * 100.0);
             }
@@ -85,7 +82,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("---------------------------------------------------");
     
     let npi = "1487648176";
-    let procedures = vec![
+    let procedures = [
         ("99213", "Office visit - Low complexity"),
         ("99214", "Office visit - Moderate complexity"),
         ("99215", "Office visit - High complexity"),
@@ -94,26 +91,37 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ];
 
     println!("Provider NPI: {}", npi);
-    
-    for (code, description) in procedures {
-        let request = LikelihoodRequest::builder()
-            .npis(vec![npi.to_string()])
-            .condition_code(code)
-            .code_type("CPT")
-            .build();
 
-        match client.procedures().get_likelihood(request).await {
-            Ok(response) => {
-                if let Some(data) = response.data.get(npi) {
-                    println!("\n{} ({}): {:.1}%", 
-                        description, code, data.likelihood * 100.0);
-                }
+    // Score every procedure concurrently, handling each result as it arrives
+    let codes = procedures
+        .iter()
+        .map(|(code, _)| (code.to_string(), CodeType::Cpt))
+        .collect();
+    let procedures_client = client.procedures();
+    let mut scores = procedures_client.likelihood_stream(npi, codes, 4);
+
+    let mut profile = ProviderProfileBuilder::new();
+    while let Some(result) = scores.next().await {
+        match result {
+            Ok((code, data)) => {
+                let description = procedures
+                    .iter()
+                    .find(|(c, _)| *c == code)
+                    .map_or("", |(_, description)| *description);
+                println!("\n{} ({}): {:.1}%",
+                    description, code, data.likelihood * 100.0);
+                profile = profile.add(code, CodeType::Cpt, data.likelihood);
             }
             Err(e) => {
-                println!("\nError checking {} ({}): {}", description, code, e);
+                println!("\nError checking procedure: {}", e);
             }
         }
     }
 
+    let profile = profile.build();
+    println!("\nMean likelihood: {:.1}%", profile.mean_likelihood * 100.0);
+    println!("Top procedures: {:?}", profile.top_procedures);
+    println!("Implied specialties: {:?}", profile.specialties_implied);
+
     Ok(())
 }
\ No newline at end of file
@@ -1,6 +1,10 @@
 //! Example demonstrating how to use the pricing API
 
-use docaroo_rs::{DocarooClient, models::{PricingRequest, CodeType}};
+use docaroo_rs::{
+    DocarooClient,
+    models::{PricingRequest, CodeType},
+    pricing::BulkOptions,
+};
 use std::env;
 
 #[tokio::main]
@@ -82,30 +86,31 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         ("0260", CodeType::Rc, "IV therapy"),
     ];
 
-    for (code, code_type, description) in code_examples {
-        println!("\nLooking up {} - {}", code, description);
-        
-        let request = PricingRequest::builder()
-            .npis(vec!["1043566623".to_string()])
-            .condition_code(code)
-            .code_type(code_type)
-            .build();
+    // Price every code concurrently, keeping the codes that succeed
+    let npis = vec!["1043566623".to_string()];
+    let codes: Vec<_> = code_examples
+        .iter()
+        .map(|(code, code_type, _)| (code.to_string(), *code_type))
+        .collect();
+    let options = BulkOptions::builder().fail_fast(false).build();
 
-        match client.pricing().get_in_network_rates(request).await {
-            Ok(response) => {
-                if let Some(rates) = response.data.get("1043566623") {
-                    if let Some(rate) = rates.first() {
-                        println!("  Found rate: ${:.2} avg", rate.avg_rate);
-                    } else {
-                        println!("  No rates found");
-                    }
+    match client.pricing().price_codes(&npis, &codes, options).await {
+        Ok(priced) => {
+            let rates = priced.rates_by_npi();
+            for (code, _, description) in &code_examples {
+                println!("\nLooking up {} - {}", code, description);
+
+                if let Some(error) = priced.failures.get(*code) {
+                    println!("  Error: {}", error);
+                } else if let Some(rate) = rates.get("1043566623").and_then(|r| r.get(*code)) {
+                    println!("  Found rate: ${:.2} avg", rate.avg_rate);
                 } else {
-                    println!("  No data for this NPI");
+                    println!("  No rates found");
                 }
             }
-            Err(e) => {
-                println!("  Error: {}", e);
-            }
+        }
+        Err(e) => {
+            println!("Error: {}", e);
         }
     }
 
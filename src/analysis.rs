@@ -0,0 +1,166 @@
+//! Summaries built from likelihood scores
+
+use crate::{models::CodeType, procedures::thresholds};
+
+/// Summary of how likely a provider is to perform a set of procedures
+///
+/// Built with [`ProviderProfileBuilder`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderProfile {
+    /// Highest-scoring procedure codes with their likelihood, most likely first
+    pub top_procedures: Vec<(String, f64)>,
+    /// Mean likelihood across every recorded score, or `0.0` if none were recorded
+    pub mean_likelihood: f64,
+    /// Specialties implied by codes scoring at least
+    /// [`thresholds::LIKELY`], most likely first, without duplicates
+    pub specialties_implied: Vec<String>,
+}
+
+/// Accumulates likelihood scores for one provider into a [`ProviderProfile`]
+///
+/// # Example
+///
+/// ```
+/// use docaroo_rs::{analysis::ProviderProfileBuilder, models::CodeType};
+///
+/// let profile = ProviderProfileBuilder::new()
+///     .add("99214", CodeType::Cpt, 0.92)
+///     .add("90834", CodeType::Cpt, 0.15)
+///     .build();
+///
+/// assert_eq!(profile.top_procedures[0], ("99214".to_string(), 0.92));
+/// assert_eq!(profile.specialties_implied, vec!["Primary care"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProviderProfileBuilder {
+    entries: Vec<(String, CodeType, f64)>,
+    top_n: usize,
+}
+
+impl Default for ProviderProfileBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProviderProfileBuilder {
+    /// Number of procedures kept in [`ProviderProfile::top_procedures`] by default
+    pub const DEFAULT_TOP_N: usize = 5;
+
+    /// Create an empty builder
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            top_n: Self::DEFAULT_TOP_N,
+        }
+    }
+
+    /// Record the likelihood score for one code
+    ///
+    /// NaN scores are ignored.
+    pub fn add(mut self, code: impl Into<String>, code_type: CodeType, likelihood: f64) -> Self {
+        if !likelihood.is_nan() {
+            self.entries.push((code.into(), code_type, likelihood));
+        }
+        self
+    }
+
+    /// Set how many procedures to keep in [`ProviderProfile::top_procedures`]
+    pub fn top_n(mut self, top_n: usize) -> Self {
+        self.top_n = top_n;
+        self
+    }
+
+    /// Summarize the recorded scores
+    pub fn build(mut self) -> ProviderProfile {
+        self.entries.sort_by(|a, b| b.2.total_cmp(&a.2));
+
+        let mean_likelihood = if self.entries.is_empty() {
+            0.0
+        } else {
+            self.entries.iter().map(|(_, _, likelihood)| likelihood).sum::<f64>()
+                / self.entries.len() as f64
+        };
+
+        let mut specialties_implied: Vec<String> = Vec::new();
+        for (code, code_type, likelihood) in &self.entries {
+            if *likelihood < thresholds::LIKELY {
+                break;
+            }
+            if let Some(specialty) = implied_specialty(code, *code_type) {
+                if !specialties_implied.iter().any(|s| s == specialty) {
+                    specialties_implied.push(specialty.to_string());
+                }
+            }
+        }
+
+        let top_procedures = self
+            .entries
+            .into_iter()
+            .take(self.top_n)
+            .map(|(code, _, likelihood)| (code, likelihood))
+            .collect();
+
+        ProviderProfile {
+            top_procedures,
+            mean_likelihood,
+            specialties_implied,
+        }
+    }
+}
+
+/// Specialty most associated with a billing code, for a small set of common codes
+fn implied_specialty(code: &str, code_type: CodeType) -> Option<&'static str> {
+    let specialty = match (code_type, code) {
+        (CodeType::Cpt, "99202" | "99203" | "99204" | "99205") => "Primary care",
+        (CodeType::Cpt, "99211" | "99212" | "99213" | "99214" | "99215") => "Primary care",
+        (CodeType::Cpt, "90832" | "90834" | "90837") => "Behavioral health",
+        (CodeType::Cpt, "20610" | "27447") => "Orthopedics",
+        (CodeType::Cpt, "36415" | "80053" | "85025") => "Laboratory",
+        (CodeType::Cpt, "93000") => "Cardiology",
+        (CodeType::Cpt, "71046" | "70553" | "77067") => "Radiology",
+        (CodeType::Cpt, "45378") => "Gastroenterology",
+        _ => return None,
+    };
+
+    Some(specialty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_from_five_scores() {
+        let profile = ProviderProfileBuilder::new()
+            .add("99213", CodeType::Cpt, 0.85)
+            .add("99214", CodeType::Cpt, 0.95)
+            .add("90834", CodeType::Cpt, 0.70)
+            .add("20610", CodeType::Cpt, 0.10)
+            .add("93000", CodeType::Cpt, 0.40)
+            .top_n(3)
+            .build();
+
+        assert_eq!(
+            profile.top_procedures,
+            vec![
+                ("99214".to_string(), 0.95),
+                ("99213".to_string(), 0.85),
+                ("90834".to_string(), 0.70),
+            ]
+        );
+        assert!((profile.mean_likelihood - 0.6).abs() < 1e-9);
+        // Orthopedics and cardiology score below the LIKELY threshold
+        assert_eq!(profile.specialties_implied, vec!["Primary care", "Behavioral health"]);
+    }
+
+    #[test]
+    fn test_empty_profile() {
+        let profile = ProviderProfileBuilder::new()
+            .add("99214", CodeType::Cpt, f64::NAN)
+            .build();
+        assert!(profile.top_procedures.is_empty());
+        assert_eq!(profile.mean_likelihood, 0.0);
+        assert!(profile.specialties_implied.is_empty());
+    }
+}
@@ -0,0 +1,127 @@
+//! Object-safe traits over the API operations, for code that should not depend on HTTP
+//!
+//! [`DocarooClient`] implements both traits. Write business logic against
+//! `&dyn PricingBackend` or `&dyn ProceduresBackend` and pass a real client in
+//! production; with the `mock` feature enabled, tests can pass a
+//! `mock::MockDocarooClient` instead.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use docaroo_rs::{
+//!     DocarooClient, Result,
+//!     backend::PricingBackend,
+//!     models::PricingRequest,
+//! };
+//!
+//! async fn cheapest_average(backend: &dyn PricingBackend, npi: &str) -> Result<Option<f64>> {
+//!     let request = PricingRequest::builder()
+//!         .npis(vec![npi.to_string()])
+//!         .condition_code("99214")
+//!         .build();
+//!     let response = backend.get_in_network_rates(request).await?;
+//!     Ok(response
+//!         .rates_for(npi)
+//!         .and_then(|rates| rates.iter().map(|rate| rate.avg_rate).reduce(f64::min)))
+//! }
+//!
+//! # async fn example() -> Result<()> {
+//! let client = DocarooClient::new("your-api-key");
+//! let cheapest = cheapest_average(&client, "1043566623").await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    client::DocarooClient,
+    error::Result,
+    models::{LikelihoodRequest, LikelihoodResponse, PricingRequest, PricingResponse},
+    transport::BoxFuture,
+};
+
+/// Source of in-network pricing data
+pub trait PricingBackend: Send + Sync {
+    /// Look up in-network rates, as [`PricingClient::get_in_network_rates`] does
+    ///
+    /// [`PricingClient::get_in_network_rates`]: crate::pricing::PricingClient::get_in_network_rates
+    fn get_in_network_rates(
+        &self,
+        request: PricingRequest,
+    ) -> BoxFuture<'_, Result<PricingResponse>>;
+}
+
+/// Source of procedure likelihood scores
+pub trait ProceduresBackend: Send + Sync {
+    /// Score procedure likelihood, as [`ProceduresClient::get_likelihood`] does
+    ///
+    /// [`ProceduresClient::get_likelihood`]: crate::procedures::ProceduresClient::get_likelihood
+    fn get_likelihood(
+        &self,
+        request: LikelihoodRequest,
+    ) -> BoxFuture<'_, Result<LikelihoodResponse>>;
+}
+
+impl PricingBackend for DocarooClient {
+    fn get_in_network_rates(
+        &self,
+        request: PricingRequest,
+    ) -> BoxFuture<'_, Result<PricingResponse>> {
+        Box::pin(async move { self.pricing().get_in_network_rates(request).await })
+    }
+}
+
+impl ProceduresBackend for DocarooClient {
+    fn get_likelihood(
+        &self,
+        request: LikelihoodRequest,
+    ) -> BoxFuture<'_, Result<LikelihoodResponse>> {
+        Box::pin(async move { self.procedures().get_likelihood(request).await })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{client::DocarooConfig, models::CodeType};
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
+
+    #[tokio::test]
+    async fn test_client_implements_backends() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/procedures/likelihood"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "1043566623": { "code": "99214", "codeType": "CPT", "likelihood": 0.7 }
+                },
+                "meta": {
+                    "requestId": "req_backend",
+                    "timestamp": "2025-06-15T23:22:22.395111Z",
+                    "processingTimeMs": 5,
+                    "outOfNetworkRecordsCount": 1
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = DocarooClient::with_config(
+            DocarooConfig::builder()
+                .api_key("test-key")
+                .base_url(server.uri())
+                .build(),
+        );
+        let backend: &dyn ProceduresBackend = &client;
+        let request = LikelihoodRequest::builder()
+            .npis(vec!["1043566623".to_string()])
+            .condition_code("99214")
+            .code_type(CodeType::Cpt)
+            .build();
+
+        let response = backend.get_likelihood(request).await.unwrap();
+        assert_eq!(response.score_for("1043566623").unwrap().likelihood, 0.7);
+    }
+}
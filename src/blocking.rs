@@ -0,0 +1,168 @@
+//! Synchronous client for callers without an async runtime (requires the `blocking`
+//! feature)
+//!
+//! The blocking clients drive the async [`DocarooClient`] on a private current-thread
+//! Tokio runtime, the same approach `reqwest::blocking` takes, so validation, retries,
+//! redirects and error handling behave exactly as they do for async callers.
+//!
+//! Like `reqwest::blocking`, these methods must not be called from within an async
+//! runtime; doing so panics.
+
+use crate::{
+    client::DocarooClient,
+    error::{DocarooError, Result},
+    models::{LikelihoodRequest, LikelihoodResponse, PricingRequest, PricingResponse},
+};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// Blocking wrapper around a [`DocarooClient`]
+///
+/// Obtain one with [`DocarooClient::blocking`]. Clones share the runtime.
+///
+/// # Example
+///
+/// ```no_run
+/// use docaroo_rs::{DocarooClient, models::PricingRequest};
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = DocarooClient::new("your-api-key").blocking()?;
+///
+/// let request = PricingRequest::builder()
+///     .npis(vec!["1043566623".to_string()])
+///     .condition_code("99214")
+///     .build();
+/// let response = client.pricing().get_in_network_rates(request)?;
+/// println!("{} providers priced", response.data.len());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct BlockingClient {
+    client: DocarooClient,
+    runtime: Arc<Runtime>,
+}
+
+impl BlockingClient {
+    /// Wrap an async client, starting the runtime that drives it
+    pub(crate) fn new(client: DocarooClient) -> Result<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| DocarooError::RuntimeError(e.to_string()))?;
+
+        Ok(Self {
+            client,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// Get the blocking pricing API client
+    pub fn pricing(&self) -> BlockingPricingClient {
+        BlockingPricingClient {
+            inner: self.clone(),
+        }
+    }
+
+    /// Get the blocking procedures API client
+    pub fn procedures(&self) -> BlockingProceduresClient {
+        BlockingProceduresClient {
+            inner: self.clone(),
+        }
+    }
+}
+
+/// Blocking counterpart of [`PricingClient`](crate::pricing::PricingClient)
+#[derive(Debug, Clone)]
+pub struct BlockingPricingClient {
+    inner: BlockingClient,
+}
+
+impl BlockingPricingClient {
+    /// Get in-network contracted rates for healthcare providers
+    ///
+    /// See [`PricingClient::get_in_network_rates`](crate::pricing::PricingClient::get_in_network_rates).
+    pub fn get_in_network_rates(&self, request: PricingRequest) -> Result<PricingResponse> {
+        let pricing = self.inner.client.pricing();
+        self.inner
+            .runtime
+            .block_on(pricing.get_in_network_rates(request))
+    }
+}
+
+/// Blocking counterpart of [`ProceduresClient`](crate::procedures::ProceduresClient)
+#[derive(Debug, Clone)]
+pub struct BlockingProceduresClient {
+    inner: BlockingClient,
+}
+
+impl BlockingProceduresClient {
+    /// Get procedure likelihood scores for healthcare providers
+    ///
+    /// See [`ProceduresClient::get_likelihood`](crate::procedures::ProceduresClient::get_likelihood).
+    pub fn get_likelihood(&self, request: LikelihoodRequest) -> Result<LikelihoodResponse> {
+        let procedures = self.inner.client.procedures();
+        self.inner.runtime.block_on(procedures.get_likelihood(request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DocarooError, client::DocarooConfig, models::CodeType};
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
+
+    #[test]
+    fn test_blocking_clients() {
+        // The mock server runs on its own runtime; the blocking client must not
+        let server_runtime = Runtime::new().unwrap();
+        let server = server_runtime.block_on(async {
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path("/procedures/likelihood"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "data": {
+                        "1487648176": { "code": "99214", "codeType": "CPT", "likelihood": 0.9 }
+                    },
+                    "meta": {
+                        "requestId": "req_blocking",
+                        "timestamp": "2025-06-15T23:22:22.395111Z",
+                        "processingTimeMs": 731,
+                        "outOfNetworkRecordsCount": 68
+                    }
+                })))
+                .mount(&server)
+                .await;
+            server
+        });
+
+        let client = DocarooClient::with_config(
+            DocarooConfig::builder()
+                .api_key("test-key")
+                .base_url(server.uri())
+                .build(),
+        )
+        .blocking()
+        .unwrap();
+
+        let request = LikelihoodRequest::builder()
+            .npis(vec!["1487648176".to_string()])
+            .condition_code("99214")
+            .code_type(CodeType::Cpt)
+            .build();
+        let response = client.procedures().get_likelihood(request).unwrap();
+        assert_eq!(response.meta.request_id, "req_blocking");
+        assert_eq!(response.data["1487648176"].likelihood, 0.9);
+
+        // Validation runs before anything is sent
+        let request = PricingRequest::builder()
+            .npis(vec!["123".to_string()])
+            .condition_code("99214")
+            .build();
+        let error = client.pricing().get_in_network_rates(request).unwrap_err();
+        assert!(matches!(error, DocarooError::InvalidRequest(_)));
+    }
+}
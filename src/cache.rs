@@ -0,0 +1,240 @@
+//! Pluggable response cache for pricing lookups
+//!
+//! Pricing data for a given set of NPIs, code and plan changes slowly, so repeated
+//! lookups can be answered from a [`Cache`] set on
+//! [`DocarooConfig::response_cache`](crate::client::DocarooConfig::response_cache).
+//! With the `cache` feature enabled, `InMemoryCache` provides a bounded LRU cache;
+//! implement [`Cache`] to back it with an external store such as Redis instead.
+
+use crate::{
+    models::{PricingRequest, PricingResponse},
+    transport::BoxFuture,
+};
+use std::{fmt::Debug, time::Duration};
+
+/// Storage for pricing responses, keyed by [`cache_key`]
+///
+/// Lookups that fail in the backing store should be reported as misses, so that a
+/// cache outage falls back to calling the API rather than failing requests.
+pub trait Cache: Debug + Send + Sync {
+    /// Return the response stored under `key`, if it is still fresh
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<PricingResponse>>;
+
+    /// Store `response` under `key` for `ttl`
+    fn insert(&self, key: String, response: PricingResponse, ttl: Duration) -> BoxFuture<'_, ()>;
+}
+
+/// Cache key for a pricing request
+///
+/// Built from the request body as sent, so requests for the same NPIs in any order,
+/// code, code type and plan share a key. Client-side fields such as NPI tags are not
+/// part of it.
+pub fn cache_key(request: &PricingRequest) -> String {
+    let body = serde_json::to_string(request).expect("pricing requests serialize to JSON");
+    format!("pricing/in-network:{body}")
+}
+
+#[cfg(feature = "cache")]
+pub use self::in_memory::InMemoryCache;
+
+#[cfg(feature = "cache")]
+mod in_memory {
+    use super::*;
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+        time::Instant,
+    };
+
+    /// In-process cache holding at most a fixed number of responses
+    ///
+    /// When full, the least recently used entry is evicted to make room. Expired
+    /// entries are dropped when they are next looked up or when room is needed.
+    /// Clones share the same entries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use docaroo_rs::{DocarooClient, cache::InMemoryCache, client::DocarooConfig};
+    /// use std::{sync::Arc, time::Duration};
+    ///
+    /// let client = DocarooClient::with_config(
+    ///     DocarooConfig::builder()
+    ///         .api_key("your-api-key")
+    ///         .response_cache(Arc::new(InMemoryCache::new(1_000)))
+    ///         .cache_ttl(Duration::from_secs(6 * 60 * 60))
+    ///         .build(),
+    /// );
+    /// ```
+    #[derive(Debug, Clone)]
+    pub struct InMemoryCache {
+        capacity: usize,
+        state: Arc<Mutex<State>>,
+    }
+
+    #[derive(Debug, Default)]
+    struct State {
+        entries: HashMap<String, Entry>,
+        /// Incremented on every access; entries remember when they were last used
+        clock: u64,
+    }
+
+    #[derive(Debug)]
+    struct Entry {
+        response: PricingResponse,
+        /// `None` if the TTL is too long to represent, i.e. the entry never expires
+        expires_at: Option<Instant>,
+        last_used: u64,
+    }
+
+    impl Entry {
+        fn is_fresh(&self, now: Instant) -> bool {
+            self.expires_at.is_none_or(|expires_at| expires_at > now)
+        }
+    }
+
+    impl InMemoryCache {
+        /// Create a cache holding at most `capacity` responses
+        ///
+        /// A capacity of zero caches nothing.
+        pub fn new(capacity: usize) -> Self {
+            Self {
+                capacity,
+                state: Arc::default(),
+            }
+        }
+
+        /// Number of responses currently stored, including expired ones not yet dropped
+        pub fn len(&self) -> usize {
+            self.state.lock().unwrap().entries.len()
+        }
+
+        /// Check whether no responses are stored
+        pub fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+
+        /// Drop every stored response
+        pub fn clear(&self) {
+            self.state.lock().unwrap().entries.clear();
+        }
+    }
+
+    impl State {
+        /// Make room for one more entry, preferring expired entries over live ones
+        fn evict(&mut self, capacity: usize, now: Instant) {
+            self.entries.retain(|_, entry| entry.is_fresh(now));
+            if self.entries.len() < capacity {
+                return;
+            }
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(key) = oldest {
+                self.entries.remove(&key);
+            }
+        }
+    }
+
+    impl Cache for InMemoryCache {
+        fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<PricingResponse>> {
+            let mut state = self.state.lock().unwrap();
+            state.clock += 1;
+            let clock = state.clock;
+
+            let response = match state.entries.get_mut(key) {
+                Some(entry) if entry.is_fresh(Instant::now()) => {
+                    entry.last_used = clock;
+                    Some(entry.response.clone())
+                }
+                Some(_) => {
+                    state.entries.remove(key);
+                    None
+                }
+                None => None,
+            };
+            Box::pin(std::future::ready(response))
+        }
+
+        fn insert(
+            &self,
+            key: String,
+            response: PricingResponse,
+            ttl: Duration,
+        ) -> BoxFuture<'_, ()> {
+            if self.capacity > 0 {
+                let now = Instant::now();
+                let mut state = self.state.lock().unwrap();
+                state.clock += 1;
+                if state.entries.len() >= self.capacity && !state.entries.contains_key(&key) {
+                    state.evict(self.capacity, now);
+                }
+                let entry = Entry {
+                    response,
+                    expires_at: now.checked_add(ttl),
+                    last_used: state.clock,
+                };
+                state.entries.insert(key, entry);
+            }
+            Box::pin(std::future::ready(()))
+        }
+    }
+}
+
+#[cfg(all(test, feature = "cache"))]
+mod tests {
+    use super::*;
+    use crate::models::{NpiMap, PricingMeta};
+
+    fn response(request_id: &str) -> PricingResponse {
+        PricingResponse {
+            data: NpiMap::default(),
+            meta: PricingMeta {
+                request_id: request_id.to_string(),
+                ..PricingMeta::default()
+            },
+            ..PricingResponse::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_cache_evicts_least_recently_used() {
+        let cache = InMemoryCache::new(2);
+        let ttl = Duration::from_secs(60);
+        cache.insert("a".to_string(), response("req_a"), ttl).await;
+        cache.insert("b".to_string(), response("req_b"), ttl).await;
+
+        // Touch "a" so that "b" is the least recently used
+        assert_eq!(cache.get("a").await.unwrap().meta.request_id, "req_a");
+        cache.insert("c".to_string(), response("req_c"), ttl).await;
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("b").await.is_none());
+        assert!(cache.get("a").await.is_some());
+        assert!(cache.get("c").await.is_some());
+
+        // Replacing an existing key does not evict anything
+        cache.insert("c".to_string(), response("req_c2"), ttl).await;
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get("c").await.unwrap().meta.request_id, "req_c2");
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_cache_expiry_and_capacity() {
+        let cache = InMemoryCache::new(2);
+        cache.insert("stale".to_string(), response("req_stale"), Duration::ZERO).await;
+        assert!(cache.get("stale").await.is_none());
+        assert!(cache.is_empty());
+
+        cache.insert("forever".to_string(), response("req_forever"), Duration::MAX).await;
+        assert!(cache.get("forever").await.is_some());
+        cache.clear();
+        assert!(cache.is_empty());
+
+        let disabled = InMemoryCache::new(0);
+        disabled.insert("a".to_string(), response("req_a"), Duration::from_secs(60)).await;
+        assert!(disabled.is_empty());
+    }
+}
@@ -1,21 +1,41 @@
 //! Error handling for the Docaroo API client
 
 use crate::models::ErrorResponse;
+use chrono::{DateTime, Utc};
+use reqwest::StatusCode;
 use thiserror::Error;
 
 /// Result type alias for Docaroo operations
 pub type Result<T> = std::result::Result<T, DocarooError>;
 
 /// Errors that can occur when interacting with the Docaroo API
+///
+/// Marked `#[non_exhaustive]` so that new failure modes can be reported without a
+/// breaking release. A `match` outside this crate needs a `_` arm; helpers such as
+/// [`is_retryable`](Self::is_retryable) and [`status`](Self::status) classify errors
+/// without naming every variant.
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum DocarooError {
-    /// HTTP request failed
+    /// HTTP request failed for a reason other than a timeout or connection failure
+    ///
+    /// Retryable only when the request was actually sent; errors building a malformed
+    /// request are not.
     #[error("HTTP request failed: {0}")]
-    RequestFailed(#[from] reqwest::Error),
+    RequestFailed(#[source] reqwest::Error),
+
+    /// Could not connect to the API, e.g. a DNS, refused connection or TLS failure
+    #[error("Connection failed: {0}")]
+    ConnectionFailed(#[source] reqwest::Error),
 
     /// API returned an error response
-    #[error("API error: {message} (code: {code})")]
+    #[error("API error (HTTP {status}): {message} (code: {code})")]
     ApiError {
+        /// HTTP status of the response
+        ///
+        /// 200 when the SDK rejects a successful response, e.g. with
+        /// [`DocarooConfig::error_on_suspicious`](crate::client::DocarooConfig).
+        status: u16,
         /// Error code from the API
         code: String,
         /// Error message from the API
@@ -24,6 +44,15 @@ pub enum DocarooError {
         request_id: Option<String>,
     },
 
+    /// API returned a 5xx server error (other than 501 Not Implemented)
+    #[error("Server error (HTTP {status}): {message}")]
+    ServerError {
+        /// HTTP status code
+        status: u16,
+        /// Error message from the API
+        message: String,
+    },
+
     /// Invalid request parameters
     #[error("Invalid request: {0}")]
     InvalidRequest(String),
@@ -39,30 +68,92 @@ pub enum DocarooError {
     #[error("Authentication failed: {0}")]
     AuthenticationFailed(String),
 
+    /// API key is not permitted to perform the operation
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
+    /// API key lacks a scope required by the endpoint
+    #[error(
+        "API key lacks required scope: {}",
+        .required.as_deref().unwrap_or("unspecified")
+    )]
+    InsufficientScope {
+        /// Scope the endpoint requires, if the API reported it
+        required: Option<String>,
+    },
+
+    /// Request body could not be serialized to JSON
+    #[error("Failed to serialize request: {0}")]
+    SerializationError(String),
+
     /// Deserialization error
-    #[error("Failed to parse response: {0}")]
-    ParseError(String),
+    #[error("Failed to parse response: {message}")]
+    ParseError {
+        /// What failed to parse and why
+        message: String,
+        /// Start of the response body that failed to parse, when there was one
+        ///
+        /// Capped at 512 bytes so large payloads are not copied into logs.
+        body: Option<String>,
+    },
+
+    /// A successful response was not JSON, e.g. an HTML page from a misconfigured
+    /// gateway or proxy
+    #[error("Expected a JSON response but received {content_type}: {snippet}")]
+    UnexpectedContentType {
+        /// `Content-Type` of the response
+        content_type: String,
+        /// Start of the response body
+        snippet: String,
+    },
+
+    /// Request did not complete within the configured timeout
+    #[error("Request timed out")]
+    Timeout,
+
+    /// Operation was cancelled through its cancellation token
+    #[error("Request was cancelled")]
+    Cancelled,
+
+    /// The runtime driving a blocking client could not be started
+    #[error("Failed to start the blocking runtime: {0}")]
+    RuntimeError(String),
 
     /// URL parsing error
     #[error("Invalid URL: {0}")]
     UrlError(#[from] url::ParseError),
 }
 
+impl From<reqwest::Error> for DocarooError {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            Self::Timeout
+        } else if error.is_connect() {
+            Self::ConnectionFailed(error)
+        } else {
+            Self::RequestFailed(error)
+        }
+    }
+}
+
 impl DocarooError {
-    /// Create an API error from an error response
-    pub fn from_error_response(response: ErrorResponse) -> Self {
+    /// Create an API error from an error response received with HTTP `status`
+    pub fn from_error_response(response: ErrorResponse, status: StatusCode) -> Self {
         match response.error.as_str() {
-            "rate_limit_exceeded" => {
-                let retry_after = response
+            "rate_limit_exceeded" => Self::RateLimitExceeded {
+                retry_after: Self::retry_after_from_details(&response).unwrap_or(60),
+            },
+            "unauthorized" => Self::AuthenticationFailed(response.message),
+            "insufficient_scope" => Self::InsufficientScope {
+                required: response
                     .details
                     .as_ref()
-                    .and_then(|d| d.get("retryAfter"))
-                    .and_then(|v| v.as_u64())
-                    .unwrap_or(60);
-                Self::RateLimitExceeded { retry_after }
-            }
-            "unauthorized" => Self::AuthenticationFailed(response.message),
+                    .and_then(|d| d.get("requiredScope").or_else(|| d.get("scope")))
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string),
+            },
             _ => Self::ApiError {
+                status: status.as_u16(),
                 code: response.error,
                 message: response.message,
                 request_id: response.request_id,
@@ -70,12 +161,98 @@ impl DocarooError {
         }
     }
 
+    /// Read the `retryAfter` delay (in seconds) from an error response's details
+    pub(crate) fn retry_after_from_details(response: &ErrorResponse) -> Option<u64> {
+        response
+            .details
+            .as_ref()
+            .and_then(|d| d.get("retryAfter"))
+            .and_then(|v| v.as_u64())
+    }
+
+    /// Parse a `Retry-After` header value into a delay in seconds
+    ///
+    /// Accepts both forms allowed by RFC 9110: a number of seconds, or an HTTP date,
+    /// which is converted to the seconds remaining after `now` (zero if it has passed).
+    pub(crate) fn retry_after_from_header(value: &str, now: DateTime<Utc>) -> Option<u64> {
+        let value = value.trim();
+        if let Ok(seconds) = value.parse::<u64>() {
+            return Some(seconds);
+        }
+
+        let date = DateTime::parse_from_rfc2822(value).ok()?;
+        let remaining = date.with_timezone(&Utc) - now;
+        // Round partial seconds up so the caller never retries early
+        let millis = remaining.num_milliseconds().max(0) as u64;
+        Some(millis.div_ceil(1000))
+    }
+
     /// Check if this error is retryable
     pub fn is_retryable(&self) -> bool {
-        matches!(
-            self,
-            Self::RequestFailed(_) | Self::RateLimitExceeded { .. }
-        )
+        match self {
+            Self::RequestFailed(error) => error.is_request() || error.is_body(),
+            Self::Timeout
+            | Self::ConnectionFailed(_)
+            | Self::RateLimitExceeded { .. }
+            | Self::ServerError { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Check whether the error was caused by the request rather than the server
+    ///
+    /// True for requests rejected by client-side validation
+    /// ([`InvalidRequest`](Self::InvalidRequest)), for the 4xx variants
+    /// ([`AuthenticationFailed`](Self::AuthenticationFailed),
+    /// [`Forbidden`](Self::Forbidden), [`InsufficientScope`](Self::InsufficientScope),
+    /// [`RateLimitExceeded`](Self::RateLimitExceeded)), and for an
+    /// [`ApiError`](Self::ApiError) with a 4xx status.
+    pub fn is_client_error(&self) -> bool {
+        match self {
+            Self::ApiError { status, .. } => (400..500).contains(status),
+            Self::InvalidRequest(_)
+            | Self::AuthenticationFailed(_)
+            | Self::Forbidden(_)
+            | Self::InsufficientScope { .. }
+            | Self::RateLimitExceeded { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Check whether the server reported a failure on its side
+    ///
+    /// True for [`ServerError`](Self::ServerError) and for an
+    /// [`ApiError`](Self::ApiError) with a 5xx status, such as 501 Not Implemented.
+    /// Transport failures, timeouts and unparseable responses are neither client nor
+    /// server errors.
+    pub fn is_server_error(&self) -> bool {
+        match self {
+            Self::ApiError { status, .. } => (500..600).contains(status),
+            Self::ServerError { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Get the HTTP status of the response that caused the error, if there was one
+    ///
+    /// Available for [`ApiError`](Self::ApiError) and
+    /// [`ServerError`](Self::ServerError), and implied by the variants that map a
+    /// single status: 401 for [`AuthenticationFailed`](Self::AuthenticationFailed),
+    /// 403 for [`Forbidden`](Self::Forbidden) and
+    /// [`InsufficientScope`](Self::InsufficientScope), and 429 for
+    /// [`RateLimitExceeded`](Self::RateLimitExceeded). `None` for errors raised before
+    /// a response arrived or while reading it.
+    ///
+    /// An [`InvalidRequest`](Self::InvalidRequest) may come from client-side
+    /// validation or from a 400 response, so it reports `None`.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Self::ApiError { status, .. } | Self::ServerError { status, .. } => Some(*status),
+            Self::AuthenticationFailed(_) => Some(401),
+            Self::Forbidden(_) | Self::InsufficientScope { .. } => Some(403),
+            Self::RateLimitExceeded { .. } => Some(429),
+            _ => None,
+        }
     }
 
     /// Get the request ID if available (for support purposes)
@@ -90,7 +267,6 @@ impl DocarooError {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Utc;
 
     #[test]
     fn test_error_from_response() {
@@ -102,13 +278,18 @@ mod tests {
             timestamp: Some(Utc::now()),
         };
 
-        let error = DocarooError::from_error_response(error_response);
+        let error = DocarooError::from_error_response(
+            error_response,
+            StatusCode::UNPROCESSABLE_ENTITY,
+        );
         match error {
             DocarooError::ApiError {
+                status,
                 code,
                 message,
                 request_id,
             } => {
+                assert_eq!(status, 422);
                 assert_eq!(code, "bad_request");
                 assert_eq!(message, "Invalid NPI format");
                 assert_eq!(request_id, Some("req_123".to_string()));
@@ -127,7 +308,10 @@ mod tests {
             timestamp: None,
         };
 
-        let error = DocarooError::from_error_response(error_response);
+        let error = DocarooError::from_error_response(
+            error_response,
+            StatusCode::TOO_MANY_REQUESTS,
+        );
         match error {
             DocarooError::RateLimitExceeded { retry_after } => {
                 assert_eq!(retry_after, 120);
@@ -136,16 +320,155 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_retry_after_from_header() {
+        let now = DateTime::parse_from_rfc3339("2015-10-21T07:28:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(DocarooError::retry_after_from_header("120", now), Some(120));
+        assert_eq!(DocarooError::retry_after_from_header(" 0 ", now), Some(0));
+        assert_eq!(
+            DocarooError::retry_after_from_header("Wed, 21 Oct 2015 07:30:00 GMT", now),
+            Some(120)
+        );
+        // Dates in the past mean the client may retry immediately
+        assert_eq!(
+            DocarooError::retry_after_from_header("Wed, 21 Oct 2015 07:00:00 GMT", now),
+            Some(0)
+        );
+        assert_eq!(DocarooError::retry_after_from_header("soon", now), None);
+        assert_eq!(DocarooError::retry_after_from_header("-5", now), None);
+    }
+
     #[test]
     fn test_is_retryable() {
         let rate_limit_error = DocarooError::RateLimitExceeded { retry_after: 60 };
         assert!(rate_limit_error.is_retryable());
 
         let api_error = DocarooError::ApiError {
+            status: 400,
             code: "bad_request".to_string(),
             message: "Invalid request".to_string(),
             request_id: None,
         };
         assert!(!api_error.is_retryable());
+
+        let server_error = DocarooError::ServerError {
+            status: 503,
+            message: "Service unavailable".to_string(),
+        };
+        assert!(server_error.is_retryable());
+    }
+
+    #[test]
+    fn test_client_and_server_classification() {
+        let api_error = |status| DocarooError::ApiError {
+            status,
+            code: "error".to_string(),
+            message: "Something went wrong".to_string(),
+            request_id: None,
+        };
+
+        let client_errors = [
+            api_error(400),
+            api_error(409),
+            DocarooError::InvalidRequest("Condition code cannot be empty".to_string()),
+            DocarooError::AuthenticationFailed("Invalid API key".to_string()),
+            DocarooError::Forbidden("Plan not allowed".to_string()),
+            DocarooError::InsufficientScope { required: None },
+            DocarooError::RateLimitExceeded { retry_after: 60 },
+        ];
+        for error in &client_errors {
+            assert!(error.is_client_error(), "{error:?}");
+            assert!(!error.is_server_error(), "{error:?}");
+        }
+
+        let server_errors = [
+            api_error(501),
+            DocarooError::ServerError {
+                status: 503,
+                message: "Service unavailable".to_string(),
+            },
+        ];
+        for error in &server_errors {
+            assert!(error.is_server_error(), "{error:?}");
+            assert!(!error.is_client_error(), "{error:?}");
+        }
+
+        for error in [
+            api_error(200),
+            DocarooError::Timeout,
+            DocarooError::ParseError {
+                message: "expected value".to_string(),
+                body: None,
+            },
+            DocarooError::Cancelled,
+        ] {
+            assert!(!error.is_client_error(), "{error:?}");
+            assert!(!error.is_server_error(), "{error:?}");
+        }
+    }
+
+    #[test]
+    fn test_status() {
+        let error = DocarooError::ServerError {
+            status: 503,
+            message: "Service unavailable".to_string(),
+        };
+        assert_eq!(error.status(), Some(503));
+        assert_eq!(DocarooError::RateLimitExceeded { retry_after: 1 }.status(), Some(429));
+        assert_eq!(DocarooError::Forbidden("no".to_string()).status(), Some(403));
+        assert_eq!(DocarooError::InvalidRequest("bad".to_string()).status(), None);
+        assert_eq!(DocarooError::Timeout.status(), None);
+    }
+
+    #[test]
+    fn test_malformed_request_is_not_retryable() {
+        let error = reqwest::Client::new()
+            .get("http://exa mple.com")
+            .build()
+            .unwrap_err();
+        let error = DocarooError::from(error);
+        assert!(matches!(error, DocarooError::RequestFailed(_)));
+        assert!(!error.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_connection_failure_is_retryable() {
+        // Bind and drop a listener to find a local port nothing is listening on
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let error = reqwest::get(format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap_err();
+        let error = DocarooError::from(error);
+        assert!(matches!(error, DocarooError::ConnectionFailed(_)));
+        assert!(error.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_reqwest_timeout_becomes_timeout() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::any())
+            .respond_with(
+                wiremock::ResponseTemplate::new(200)
+                    .set_delay(std::time::Duration::from_millis(200)),
+            )
+            .mount(&server)
+            .await;
+
+        let error = reqwest::Client::new()
+            .get(server.uri())
+            .timeout(std::time::Duration::from_millis(20))
+            .send()
+            .await
+            .unwrap_err();
+        let error = DocarooError::from(error);
+        assert!(matches!(error, DocarooError::Timeout));
+        assert!(error.is_retryable());
     }
 }
\ No newline at end of file
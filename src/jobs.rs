@@ -0,0 +1,265 @@
+//! Declarative pricing runs across many codes and NPIs
+
+use crate::{
+    client::DocarooClient,
+    error::Result,
+    models::{CodeType, PricingRequest, RateData},
+    pricing::MAX_NPIS_PER_PRICING_REQUEST,
+};
+use bon::Builder;
+use futures::{StreamExt, TryStreamExt};
+use std::collections::{HashMap, HashSet};
+
+/// A pricing run over every combination of a set of NPIs and billing codes
+///
+/// Declare the run once, then [`execute_pricing`](Self::execute_pricing) fans it out
+/// into one request per code and chunk of [`MAX_NPIS_PER_PRICING_REQUEST`] NPIs.
+///
+/// # Example
+///
+/// ```no_run
+/// use docaroo_rs::{DocarooClient, jobs::JobPlan, models::CodeType};
+///
+/// # async fn example(npis: Vec<String>) -> Result<(), Box<dyn std::error::Error>> {
+/// let client = DocarooClient::new("your-api-key");
+///
+/// let plan = JobPlan::builder()
+///     .npis(npis)
+///     .codes(vec![
+///         ("99213".to_string(), CodeType::Cpt),
+///         ("99214".to_string(), CodeType::Cpt),
+///     ])
+///     .plan_id("942404110")
+///     .build();
+///
+/// let results = plan.execute_pricing(&client, 4).await?;
+/// if let Some(rates) = results.get("99214", "1043566623") {
+///     println!("{} rates", rates.len());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Builder)]
+pub struct JobPlan {
+    /// NPIs to price; duplicates are priced once
+    #[builder(into)]
+    pub npis: Vec<String>,
+
+    /// Billing codes to price each NPI for, with their code types
+    #[builder(into)]
+    pub codes: Vec<(String, CodeType)>,
+
+    /// Insurance plan to price against (the API default plan if unset)
+    #[builder(into)]
+    pub plan_id: Option<String>,
+}
+
+impl JobPlan {
+    /// Build one pricing request per code and chunk of NPIs
+    pub fn requests(&self) -> Vec<PricingRequest> {
+        let mut seen = HashSet::new();
+        let npis: Vec<&String> = self
+            .npis
+            .iter()
+            .filter(|npi| seen.insert(npi.as_str()))
+            .collect();
+
+        self.codes
+            .iter()
+            .flat_map(|(code, code_type)| {
+                npis.chunks(MAX_NPIS_PER_PRICING_REQUEST).map(|chunk| {
+                    PricingRequest::builder()
+                        .npis(chunk.iter().map(|npi| npi.to_string()).collect::<Vec<_>>())
+                        .condition_code(code.clone())
+                        .maybe_plan_id(self.plan_id.clone())
+                        .code_type(*code_type)
+                        .build()
+                })
+            })
+            .collect()
+    }
+
+    /// Price every NPI for every code, with at most `concurrency` requests in flight
+    ///
+    /// Every request is validated before any is sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first validation or request error; partial results are discarded.
+    pub async fn execute_pricing(
+        &self,
+        client: &DocarooClient,
+        concurrency: usize,
+    ) -> Result<JobResults> {
+        let pricing = client.pricing();
+        let requests = self.requests();
+        for request in &requests {
+            pricing.validate_pricing_request(request)?;
+        }
+
+        let responses = futures::stream::iter(requests)
+            .map(|request| {
+                let pricing = &pricing;
+                async move {
+                    let code = request.condition_code.clone();
+                    pricing
+                        .get_in_network_rates(request)
+                        .await
+                        .map(|response| (code, response))
+                }
+            })
+            .buffered(concurrency.max(1))
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let mut results = JobResults::default();
+        for (code, response) in responses {
+            results.request_ids.push(response.meta.request_id);
+            for (npi, rates) in response.data {
+                results
+                    .rates
+                    .entry((code.clone(), npi))
+                    .or_default()
+                    .extend(rates);
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+/// Rates gathered by [`JobPlan::execute_pricing`], indexed by code and NPI
+#[derive(Debug, Clone, Default)]
+pub struct JobResults {
+    /// Rates keyed by `(code, npi)`; NPIs the API returned nothing for are absent
+    pub rates: HashMap<(String, String), Vec<RateData>>,
+    /// Request ID of every request sent, in request order
+    pub request_ids: Vec<String>,
+}
+
+impl JobResults {
+    /// Rates returned for an NPI under a code
+    pub fn get(&self, code: &str, npi: &str) -> Option<&[RateData]> {
+        self.rates
+            .get(&(code.to_string(), npi.to_string()))
+            .map(Vec::as_slice)
+    }
+
+    /// Number of code and NPI combinations with rates
+    pub fn len(&self) -> usize {
+        self.rates.len()
+    }
+
+    /// Check whether no combination returned rates
+    pub fn is_empty(&self) -> bool {
+        self.rates.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::DocarooConfig;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
+
+    /// Respond with one rate for each requested NPI, priced under the requested code
+    fn echo_rates(request: &wiremock::Request) -> ResponseTemplate {
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        let code = body["conditionCode"].as_str().unwrap();
+        let data: serde_json::Map<String, serde_json::Value> = body["npis"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|npi| {
+                let rate = serde_json::json!([{
+                    "code": code,
+                    "codeType": "CPT",
+                    "negotiatedType": "negotiated",
+                    "minRate": 65.87,
+                    "maxRate": 266.88,
+                    "avgRate": 147.03,
+                    "instances": 6
+                }]);
+                (npi.as_str().unwrap().to_string(), rate)
+            })
+            .collect();
+
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": data,
+            "meta": {
+                "planId": "942404110",
+                "payer": "UNH",
+                "requestId": format!("req_{code}_{}", body["npis"][0].as_str().unwrap()),
+                "timestamp": "2025-06-15T23:15:48.734729Z",
+                "processingTimeMs": 100,
+                "inNetworkRecordsCount": 1
+            }
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_execute_pricing_fans_out_codes_and_chunks() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .respond_with(echo_rates)
+            .expect(4) // 2 codes x (10 + 2 NPIs)
+            .mount(&server)
+            .await;
+        let client = DocarooClient::with_config(
+            DocarooConfig::builder()
+                .api_key("test-key")
+                .base_url(server.uri())
+                .build(),
+        );
+
+        let npis: Vec<String> = (0..12)
+            .map(|i| crate::validation::npi_with_check_digit(100_000_000 + i))
+            .collect();
+        let plan = JobPlan::builder()
+            .npis(npis.clone())
+            .codes(vec![
+                ("99213".to_string(), CodeType::Cpt),
+                ("99214".to_string(), CodeType::Cpt),
+            ])
+            .plan_id("942404110")
+            .build();
+
+        let results = plan.execute_pricing(&client, 2).await.unwrap();
+        assert_eq!(results.len(), 24);
+        assert_eq!(results.request_ids.len(), 4);
+        for code in ["99213", "99214"] {
+            for npi in &npis {
+                let rates = results.get(code, npi).unwrap();
+                assert_eq!(rates.len(), 1);
+                assert_eq!(rates[0].code, code);
+            }
+        }
+        assert!(results.get("99215", &npis[0]).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_execute_pricing_validates_before_sending() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(echo_rates)
+            .expect(0)
+            .mount(&server)
+            .await;
+        let client = DocarooClient::with_config(
+            DocarooConfig::builder()
+                .api_key("test-key")
+                .base_url(server.uri())
+                .build(),
+        );
+
+        let plan = JobPlan::builder()
+            .npis(vec!["1043566623".to_string(), "123".to_string()])
+            .codes(vec![("99214".to_string(), CodeType::Cpt)])
+            .build();
+        assert!(plan.execute_pricing(&client, 4).await.is_err());
+    }
+}
@@ -38,18 +38,39 @@
 //! }
 //! ```
 
+pub mod analysis;
+pub mod backend;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod cache;
 pub mod client;
 pub mod error;
+pub mod jobs;
+#[cfg(feature = "prometheus")]
+pub mod metrics;
+#[cfg(feature = "mock")]
+pub mod mock;
 pub mod models;
+pub mod payers;
 pub mod pricing;
 pub mod procedures;
+pub mod retry;
+pub mod stats;
+pub mod transport;
+pub mod validation;
 
 pub use client::DocarooClient;
 pub use error::{DocarooError, Result};
+pub use pricing::MAX_NPIS_PER_PRICING_REQUEST;
+pub use procedures::MAX_NPIS_PER_LIKELIHOOD_REQUEST;
+pub use tokio_util::sync::CancellationToken;
 
 /// The base URL for the Docaroo API
 pub const API_BASE_URL: &str = "https://care-navigation-gateway-ccg16t89.wl.gateway.dev";
 
+/// Default `User-Agent` sent with every request, e.g. `docaroo-rs/0.0.1`
+pub const USER_AGENT: &str = concat!("docaroo-rs/", env!("CARGO_PKG_VERSION"));
+
 /// Re-export commonly used types
 pub mod prelude {
     pub use crate::{
@@ -0,0 +1,85 @@
+//! Prometheus metrics for API requests (requires the `prometheus` feature)
+
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry, TextEncoder,
+};
+use std::{fmt, time::Duration};
+
+/// Request metrics recorded by a [`DocarooClient`](crate::DocarooClient)
+///
+/// Each client owns its own registry, shared by its clones, containing:
+///
+/// - `docaroo_requests_total{endpoint, status}`: HTTP requests sent, by endpoint and
+///   response status code (`error` when no response was received)
+/// - `docaroo_request_duration_seconds{endpoint}`: request latency histogram
+///
+/// Retries and redirects count as separate requests.
+pub struct Metrics {
+    registry: Registry,
+    requests_total: IntCounterVec,
+    request_duration_seconds: HistogramVec,
+}
+
+impl Metrics {
+    /// Create a fresh registry with the client's metrics registered
+    pub(crate) fn new() -> Self {
+        let requests_total = IntCounterVec::new(
+            Opts::new("docaroo_requests_total", "Docaroo API requests sent"),
+            &["endpoint", "status"],
+        )
+        .expect("valid counter definition");
+        let request_duration_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "docaroo_request_duration_seconds",
+                "Docaroo API request latency in seconds",
+            ),
+            &["endpoint"],
+        )
+        .expect("valid histogram definition");
+
+        let registry = Registry::new();
+        registry
+            .register(Box::new(requests_total.clone()))
+            .expect("metric registered once");
+        registry
+            .register(Box::new(request_duration_seconds.clone()))
+            .expect("metric registered once");
+
+        Self {
+            registry,
+            requests_total,
+            request_duration_seconds,
+        }
+    }
+
+    /// Record one request to `endpoint` that finished with `status` after `elapsed`
+    pub(crate) fn observe(&self, endpoint: &str, status: Option<u16>, elapsed: Duration) {
+        let status = status.map_or_else(|| "error".to_string(), |status| status.to_string());
+        self.requests_total
+            .with_label_values(&[endpoint, status.as_str()])
+            .inc();
+        self.request_duration_seconds
+            .with_label_values(&[endpoint])
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// The registry holding the metrics, e.g. for merging into an application registry
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// Render all metrics in the Prometheus text exposition format
+    pub fn text(&self) -> String {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("text encoding does not fail");
+        String::from_utf8(buffer).expect("text exposition is UTF-8")
+    }
+}
+
+impl fmt::Debug for Metrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Metrics").finish_non_exhaustive()
+    }
+}
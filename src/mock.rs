@@ -0,0 +1,303 @@
+//! In-memory stand-in for [`DocarooClient`](crate::DocarooClient) in downstream tests
+//!
+//! Enabled by the `mock` feature. [`MockDocarooClient`] implements
+//! [`PricingBackend`] and [`ProceduresBackend`] from canned data registered per NPI and
+//! billing code, so code written against those traits can be tested without a
+//! network.
+
+use crate::{
+    backend::{PricingBackend, ProceduresBackend},
+    error::Result,
+    models::{
+        LikelihoodData, LikelihoodMeta, LikelihoodRequest, LikelihoodResponse, NpiMap, PricingMeta,
+        PricingRequest, PricingResponse, RateData,
+    },
+    transport::BoxFuture,
+};
+use chrono::Utc;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+/// Client that answers pricing and likelihood requests from registered data
+///
+/// Each request is validated like the real client would validate it, then answered
+/// with the data registered for each requested NPI under the request's condition
+/// code. NPIs with nothing registered are left out of the response, as the API does
+/// for providers without data. Every request received is recorded for later
+/// assertions.
+///
+/// # Example
+///
+/// ```
+/// use docaroo_rs::{
+///     backend::ProceduresBackend,
+///     mock::MockDocarooClient,
+///     models::{CodeType, LikelihoodRequest},
+/// };
+///
+/// # tokio_test::block_on(async {
+/// let mock = MockDocarooClient::builder()
+///     .likelihood("1043566623", "99214", 0.92)
+///     .build();
+///
+/// let request = LikelihoodRequest::builder()
+///     .npis(vec!["1043566623".to_string(), "1972767655".to_string()])
+///     .condition_code("99214")
+///     .code_type(CodeType::Cpt)
+///     .build();
+/// let response = mock.get_likelihood(request).await.unwrap();
+///
+/// assert_eq!(response.score_for("1043566623").unwrap().likelihood, 0.92);
+/// assert!(response.score_for("1972767655").is_none());
+/// assert_eq!(mock.likelihood_requests().len(), 1);
+/// # });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockDocarooClient {
+    rates: HashMap<(String, String), Vec<RateData>>,
+    likelihoods: HashMap<(String, String), f64>,
+    pricing_requests: Arc<Mutex<Vec<PricingRequest>>>,
+    likelihood_requests: Arc<Mutex<Vec<LikelihoodRequest>>>,
+}
+
+impl MockDocarooClient {
+    /// Start registering canned responses
+    pub fn builder() -> MockDocarooClientBuilder {
+        MockDocarooClientBuilder::default()
+    }
+
+    /// Pricing requests received so far, in order
+    ///
+    /// Shared by every clone of this client.
+    pub fn pricing_requests(&self) -> Vec<PricingRequest> {
+        self.pricing_requests.lock().unwrap().clone()
+    }
+
+    /// Likelihood requests received so far, in order
+    ///
+    /// Shared by every clone of this client.
+    pub fn likelihood_requests(&self) -> Vec<LikelihoodRequest> {
+        self.likelihood_requests.lock().unwrap().clone()
+    }
+
+    fn request_id(prefix: &str, count: usize) -> String {
+        format!("{prefix}_mock_{count}")
+    }
+}
+
+impl PricingBackend for MockDocarooClient {
+    fn get_in_network_rates(
+        &self,
+        request: PricingRequest,
+    ) -> BoxFuture<'_, Result<PricingResponse>> {
+        Box::pin(async move {
+            request.validate()?;
+
+            let data: NpiMap<Vec<RateData>> = request
+                .npis
+                .iter()
+                .filter_map(|npi| {
+                    self.rates
+                        .get(&(npi.clone(), request.condition_code.clone()))
+                        .map(|rates| (npi.clone(), rates.clone()))
+                })
+                .collect();
+
+            let mut requests = self.pricing_requests.lock().unwrap();
+            requests.push(request.clone());
+            let meta = PricingMeta {
+                plan_id: request.plan_id.clone().unwrap_or_default(),
+                request_id: Self::request_id("pricing", requests.len()),
+                timestamp: Some(Utc::now()),
+                in_network_records_count: data.values().map(Vec::len).sum::<usize>() as u32,
+                ..PricingMeta::default()
+            };
+
+            Ok(PricingResponse {
+                data,
+                meta,
+                requested_plan_id: request.plan_id,
+                parse_errors: HashMap::new(),
+                extra: HashMap::new(),
+            })
+        })
+    }
+}
+
+impl ProceduresBackend for MockDocarooClient {
+    fn get_likelihood(
+        &self,
+        request: LikelihoodRequest,
+    ) -> BoxFuture<'_, Result<LikelihoodResponse>> {
+        Box::pin(async move {
+            request.validate()?;
+
+            let data: NpiMap<LikelihoodData> = request
+                .npis
+                .iter()
+                .filter_map(|npi| {
+                    self.likelihoods
+                        .get(&(npi.clone(), request.condition_code.clone()))
+                        .map(|&likelihood| {
+                            let data = LikelihoodData {
+                                code: request.condition_code.clone(),
+                                code_type: request.code_type.to_string(),
+                                likelihood,
+                            };
+                            (npi.clone(), data)
+                        })
+                })
+                .collect();
+
+            let mut requests = self.likelihood_requests.lock().unwrap();
+            requests.push(request);
+            let meta = LikelihoodMeta {
+                request_id: Self::request_id("likelihood", requests.len()),
+                timestamp: Some(Utc::now()),
+                timestamp_raw: None,
+                processing_time_ms: 0,
+                out_of_network_records_count: data.len() as u32,
+            };
+
+            Ok(LikelihoodResponse {
+                data,
+                meta,
+                extra: HashMap::new(),
+            })
+        })
+    }
+}
+
+/// Registers canned data for a [`MockDocarooClient`]
+///
+/// Registering the same NPI and code twice keeps the later value.
+#[derive(Debug, Clone, Default)]
+pub struct MockDocarooClientBuilder {
+    rates: HashMap<(String, String), Vec<RateData>>,
+    likelihoods: HashMap<(String, String), f64>,
+}
+
+impl MockDocarooClientBuilder {
+    /// Return `rates` for `npi` whenever `code` is priced
+    pub fn rates(
+        mut self,
+        npi: impl Into<String>,
+        code: impl Into<String>,
+        rates: Vec<RateData>,
+    ) -> Self {
+        self.rates.insert((npi.into(), code.into()), rates);
+        self
+    }
+
+    /// Return a likelihood score for `npi` whenever `code` is scored
+    ///
+    /// The returned [`LikelihoodData`] takes its code and code type from the request.
+    pub fn likelihood(
+        mut self,
+        npi: impl Into<String>,
+        code: impl Into<String>,
+        likelihood: f64,
+    ) -> Self {
+        self.likelihoods
+            .insert((npi.into(), code.into()), likelihood);
+        self
+    }
+
+    /// Create the mock client
+    pub fn build(self) -> MockDocarooClient {
+        MockDocarooClient {
+            rates: self.rates,
+            likelihoods: self.likelihoods,
+            ..MockDocarooClient::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DocarooError, models::CodeType};
+
+    fn rate(avg_rate: f64) -> RateData {
+        serde_json::from_value(serde_json::json!({
+            "code": "99214",
+            "codeType": "CPT",
+            "negotiatedType": "negotiated",
+            "minRate": avg_rate - 10.0,
+            "maxRate": avg_rate + 10.0,
+            "avgRate": avg_rate,
+            "instances": 3
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_pricing_answers_registered_npis() {
+        let mock = MockDocarooClient::builder()
+            .rates("1043566623", "99214", vec![rate(147.03)])
+            .rates("1043566623", "99213", vec![rate(98.5)])
+            .rates("1972767655", "99214", vec![rate(150.25), rate(120.0)])
+            .build();
+        let backend: &dyn PricingBackend = &mock;
+
+        let request = PricingRequest::builder()
+            .npis(vec![
+                "1043566623".to_string(),
+                "1972767655".to_string(),
+                "1487648176".to_string(),
+            ])
+            .condition_code("99214")
+            .plan_id("942404110")
+            .build();
+        let response = backend.get_in_network_rates(request).await.unwrap();
+
+        assert_eq!(response.data.len(), 2);
+        assert_eq!(
+            response.rates_for("1043566623").unwrap()[0].avg_rate,
+            147.03
+        );
+        assert_eq!(response.rates_for("1972767655").unwrap().len(), 2);
+        assert!(response.rates_for("1487648176").is_none());
+        assert_eq!(response.meta.plan_id, "942404110");
+        assert_eq!(response.meta.in_network_records_count, 3);
+        assert_eq!(response.meta.request_id, "pricing_mock_1");
+
+        let requests = mock.clone().pricing_requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].npis.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_likelihood_uses_request_code_type() {
+        let mock = MockDocarooClient::builder()
+            .likelihood("1043566623", "D0120", 0.4)
+            .build();
+
+        let request = LikelihoodRequest::builder()
+            .npis(vec!["1043566623".to_string()])
+            .condition_code("D0120")
+            .code_type(CodeType::Cdt)
+            .build();
+        let response = mock.get_likelihood(request).await.unwrap();
+
+        let data = response.score_for("1043566623").unwrap();
+        assert_eq!(data.code, "D0120");
+        assert_eq!(data.code_type, "CDT");
+        assert_eq!(data.likelihood, 0.4);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_requests_are_rejected() {
+        let mock = MockDocarooClient::builder().build();
+        let request = PricingRequest::builder()
+            .npis(vec!["1234567890".to_string()])
+            .condition_code("99214")
+            .build();
+
+        let err = mock.get_in_network_rates(request).await.unwrap_err();
+        assert!(matches!(err, DocarooError::InvalidRequest(_)));
+        assert!(mock.pricing_requests().is_empty());
+    }
+}
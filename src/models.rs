@@ -5,11 +5,22 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Map from NPI to per-provider data in API responses
+///
+/// Iterates in the order NPIs appeared in the response JSON, which keeps reports
+/// reproducible.
+pub type NpiMap<V> = indexmap::IndexMap<String, V>;
+
 /// Medical billing code types supported by the API
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Marked `#[non_exhaustive]` so that code types the API adds can be supported
+/// without a breaking release; a `match` outside this crate needs a `_` arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING-KEBAB-CASE")]
+#[non_exhaustive]
 pub enum CodeType {
     /// Current Procedural Terminology
+    #[default]
     #[serde(rename = "CPT")]
     Cpt,
     /// National Drug Code
@@ -62,19 +73,427 @@ pub enum CodeType {
     CstmAll,
 }
 
-impl Default for CodeType {
-    fn default() -> Self {
-        Self::Cpt
+impl CodeType {
+    /// Canonical wire representation of the code type, e.g. `"MS-DRG"`
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Cpt => "CPT",
+            Self::Ndc => "NDC",
+            Self::Hcpcs => "HCPCS",
+            Self::Rc => "RC",
+            Self::Icd => "ICD",
+            Self::MsDrg => "MS-DRG",
+            Self::RDrg => "R-DRG",
+            Self::SDrg => "S-DRG",
+            Self::ApsDrg => "APS-DRG",
+            Self::ApDrg => "AP-DRG",
+            Self::AprDrg => "APR-DRG",
+            Self::Apc => "APC",
+            Self::Local => "LOCAL",
+            Self::Eapg => "EAPG",
+            Self::Hipps => "HIPPS",
+            Self::Cdt => "CDT",
+            Self::CstmAll => "CSTM-ALL",
+        }
+    }
+
+    /// Every supported code type, in declaration order
+    pub const fn all() -> &'static [CodeType] {
+        &[
+            Self::Cpt,
+            Self::Ndc,
+            Self::Hcpcs,
+            Self::Rc,
+            Self::Icd,
+            Self::MsDrg,
+            Self::RDrg,
+            Self::SDrg,
+            Self::ApsDrg,
+            Self::ApDrg,
+            Self::AprDrg,
+            Self::Apc,
+            Self::Local,
+            Self::Eapg,
+            Self::Hipps,
+            Self::Cdt,
+            Self::CstmAll,
+        ]
+    }
+
+    /// Leniently parse a code type, accepting common aliases
+    ///
+    /// Matching ignores case and any `-`, `_` or space separators, so `"ms_drg"` and
+    /// `"MsDrg"` both resolve to [`CodeType::MsDrg`]. On top of the canonical names,
+    /// these aliases are accepted:
+    ///
+    /// | Alias | Code type |
+    /// |-------|-----------|
+    /// | `drg` | [`MsDrg`](CodeType::MsDrg) |
+    /// | `proc`, `procedure`, `cpt4` | [`Cpt`](CodeType::Cpt) |
+    /// | `drug` | [`Ndc`](CodeType::Ndc) |
+    /// | `rev`, `revenue`, `revcode` | [`Rc`](CodeType::Rc) |
+    /// | `icd10` | [`Icd`](CodeType::Icd) |
+    /// | `dental` | [`Cdt`](CodeType::Cdt) |
+    ///
+    /// Intended for user-facing input such as CLI arguments; API payloads should use
+    /// the exact wire names.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DocarooError::InvalidRequest`](crate::DocarooError::InvalidRequest) if
+    /// the input matches no code type or alias.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use docaroo_rs::models::CodeType;
+    ///
+    /// assert_eq!(CodeType::from_alias("drg").unwrap(), CodeType::MsDrg);
+    /// assert_eq!(CodeType::from_alias("apr-drg").unwrap(), CodeType::AprDrg);
+    /// assert!(CodeType::from_alias("xyz").is_err());
+    /// ```
+    pub fn from_alias(input: &str) -> crate::Result<Self> {
+        let normalized: String = input
+            .chars()
+            .filter(|c| !matches!(c, '-' | '_' | ' '))
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+
+        let canonical = Self::all()
+            .iter()
+            .copied()
+            .find(|code_type| code_type.as_str().replace('-', "") == normalized);
+        if let Some(code_type) = canonical {
+            return Ok(code_type);
+        }
+
+        match normalized.as_str() {
+            "DRG" => Ok(Self::MsDrg),
+            "PROC" | "PROCEDURE" | "CPT4" => Ok(Self::Cpt),
+            "DRUG" => Ok(Self::Ndc),
+            "REV" | "REVENUE" | "REVCODE" => Ok(Self::Rc),
+            "ICD10" => Ok(Self::Icd),
+            "DENTAL" => Ok(Self::Cdt),
+            _ => Err(crate::DocarooError::InvalidRequest(format!(
+                "Unknown code type: '{}'",
+                input
+            ))),
+        }
+    }
+}
+
+impl std::str::FromStr for CodeType {
+    type Err = crate::DocarooError;
+
+    /// Parse a code type from its wire name, e.g. `"CPT"` or `"ms-drg"`, ignoring case
+    ///
+    /// Use [`CodeType::from_alias`] to also accept aliases such as `"drg"`.
+    fn from_str(s: &str) -> crate::Result<Self> {
+        Self::all()
+            .iter()
+            .copied()
+            .find(|code_type| code_type.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                crate::DocarooError::InvalidRequest(format!("Unknown code type: '{}'", s))
+            })
+    }
+}
+
+impl std::fmt::Display for CodeType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl TryFrom<&str> for CodeType {
+    type Error = crate::DocarooError;
+
+    fn try_from(s: &str) -> crate::Result<Self> {
+        s.parse()
+    }
+}
+
+impl AsRef<str> for CodeType {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq<str> for CodeType {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for CodeType {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+/// A [`CodeType`], or its wire name as a string
+///
+/// Accepted by
+/// [`ProceduresClient::check_providers`](crate::procedures::ProceduresClient::check_providers)
+/// so that code written against string code types keeps compiling. Strings are parsed
+/// like [`CodeType::from_str`](std::str::FromStr::from_str), ignoring case.
+pub trait IntoCodeType {
+    /// Convert into a [`CodeType`]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DocarooError::InvalidRequest`](crate::DocarooError::InvalidRequest) if
+    /// a string is not the wire name of a code type.
+    fn try_into_code_type(self) -> crate::Result<CodeType>;
+}
+
+impl IntoCodeType for CodeType {
+    fn try_into_code_type(self) -> crate::Result<CodeType> {
+        Ok(self)
     }
 }
 
+impl IntoCodeType for &str {
+    fn try_into_code_type(self) -> crate::Result<CodeType> {
+        self.parse()
+    }
+}
+
+impl IntoCodeType for String {
+    fn try_into_code_type(self) -> crate::Result<CodeType> {
+        self.parse()
+    }
+}
+
+/// Look up a human-readable description for a billing code
+///
+/// Backed by a small embedded catalog rather than a full code set: common evaluation
+/// and management, behavioral health, lab, imaging and surgical CPT codes, a few
+/// HCPCS drug and vaccine codes, and common revenue codes (including every code used
+/// in the crate's examples). Returns `None` for anything outside the catalog.
+///
+/// # Example
+///
+/// ```
+/// use docaroo_rs::models::{CodeType, code_description};
+///
+/// assert_eq!(
+///     code_description("99214", CodeType::Cpt),
+///     Some("Office visit, moderate complexity")
+/// );
+/// ```
+pub fn code_description(code: &str, code_type: CodeType) -> Option<&'static str> {
+    let description = match (code_type, code) {
+        (CodeType::Cpt, "99202") => "New patient office visit, straightforward complexity",
+        (CodeType::Cpt, "99203") => "New patient office visit, low complexity",
+        (CodeType::Cpt, "99204") => "New patient office visit, moderate complexity",
+        (CodeType::Cpt, "99205") => "New patient office visit, high complexity",
+        (CodeType::Cpt, "99211") => "Office visit, minimal complexity",
+        (CodeType::Cpt, "99212") => "Office visit, straightforward complexity",
+        (CodeType::Cpt, "99213") => "Office visit, low complexity",
+        (CodeType::Cpt, "99214") => "Office visit, moderate complexity",
+        (CodeType::Cpt, "99215") => "Office visit, high complexity",
+        (CodeType::Cpt, "90832") => "Psychotherapy, 30 minutes",
+        (CodeType::Cpt, "90834") => "Psychotherapy, 45 minutes",
+        (CodeType::Cpt, "90837") => "Psychotherapy, 60 minutes",
+        (CodeType::Cpt, "20610") => "Arthrocentesis or injection, major joint or bursa",
+        (CodeType::Cpt, "36415") => "Routine venipuncture",
+        (CodeType::Cpt, "80053") => "Comprehensive metabolic panel",
+        (CodeType::Cpt, "85025") => "Complete blood count with automated differential",
+        (CodeType::Cpt, "93000") => "Electrocardiogram with interpretation and report",
+        (CodeType::Cpt, "71046") => "Chest X-ray, 2 views",
+        (CodeType::Cpt, "70553") => "MRI brain without and with contrast",
+        (CodeType::Cpt, "77067") => "Screening mammography, bilateral",
+        (CodeType::Cpt, "45378") => "Diagnostic colonoscopy",
+        (CodeType::Cpt, "27447") => "Total knee arthroplasty",
+        (CodeType::Hcpcs, "J0180") => "Injection, agalsidase beta, 1 mg",
+        (CodeType::Hcpcs, "J1100") => "Injection, dexamethasone sodium phosphate, 1 mg",
+        (CodeType::Hcpcs, "G0008") => "Administration of influenza vaccine",
+        (CodeType::Rc, "0260") => "IV therapy, general",
+        (CodeType::Rc, "0360") => "Operating room services, general",
+        (CodeType::Rc, "0450") => "Emergency room, general",
+        _ => return None,
+    };
+
+    Some(description)
+}
+
+/// Read NPIs from a line-oriented source such as stdin
+///
+/// Expects one NPI per line. Surrounding whitespace is trimmed, blank lines are
+/// skipped, and anything after a `#` is treated as a comment.
+///
+/// # Errors
+///
+/// Returns [`DocarooError::InvalidRequest`](crate::DocarooError::InvalidRequest) with
+/// the 1-based line number of the first malformed NPI, or
+/// [`DocarooError::ParseError`](crate::DocarooError::ParseError) if reading fails.
+///
+/// # Example
+///
+/// ```no_run
+/// use docaroo_rs::models::read_npis_from_reader;
+///
+/// # fn example() -> docaroo_rs::Result<()> {
+/// let npis = read_npis_from_reader(std::io::stdin().lock())?;
+/// println!("Read {} NPIs", npis.len());
+/// # Ok(())
+/// # }
+/// ```
+pub fn read_npis_from_reader<R: std::io::BufRead>(r: R) -> crate::Result<Vec<String>> {
+    use crate::error::DocarooError;
+
+    let mut npis = Vec::new();
+    for (index, line) in r.lines().enumerate() {
+        let line = line.map_err(|e| DocarooError::ParseError {
+            message: e.to_string(),
+            body: None,
+        })?;
+        let npi = line.split('#').next().unwrap_or_default().trim();
+        if npi.is_empty() {
+            continue;
+        }
+
+        crate::validation::validate_npi(npi).map_err(|e| match e {
+            DocarooError::InvalidRequest(message) => {
+                DocarooError::InvalidRequest(format!("Line {}: {}", index + 1, message))
+            }
+            other => other,
+        })?;
+        npis.push(npi.to_string());
+    }
+
+    Ok(npis)
+}
+
+/// Entity type of a National Provider Identifier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NpiEntityType {
+    /// Type 1 NPI: an individual provider such as a physician
+    Individual,
+    /// Type 2 NPI: an organization such as a hospital or group practice
+    Organization,
+}
+
+/// Classify an NPI as individual or organizational from the number alone
+///
+/// Individual and organizational NPIs are issued from the same number range, so the
+/// digits carry no entity type and this returns `None` for every NPI. The
+/// authoritative answer is the "Entity Type Code" in the NPPES registry; pass
+/// entity types looked up there to [`PricingRequest::lint_with`].
+pub fn npi_entity_type(_npi: &str) -> Option<NpiEntityType> {
+    None
+}
+
+/// Load a list of pricing requests from a YAML job definition
+///
+/// The document must be a sequence of requests using the same camelCase field names
+/// as the JSON API (`npis`, `conditionCode`, `planId`, `codeType`). Every request is
+/// validated with [`PricingRequest::validate`].
+///
+/// # Errors
+///
+/// Returns [`DocarooError::ParseError`](crate::DocarooError::ParseError) if the YAML is
+/// malformed, or [`DocarooError::InvalidRequest`](crate::DocarooError::InvalidRequest)
+/// naming the index of the first request that fails validation.
+///
+/// # Example
+///
+/// ```
+/// use docaroo_rs::models::load_pricing_requests_yaml;
+///
+/// let requests = load_pricing_requests_yaml(
+///     r#"
+/// - npis: ["1043566623"]
+///   conditionCode: "99214"
+///   codeType: CPT
+/// "#,
+/// )
+/// .unwrap();
+/// assert_eq!(requests[0].condition_code, "99214");
+/// ```
+#[cfg(feature = "yaml")]
+pub fn load_pricing_requests_yaml(s: &str) -> crate::Result<Vec<PricingRequest>> {
+    use crate::error::DocarooError;
+
+    let requests: Vec<PricingRequest> =
+        serde_yaml::from_str(s).map_err(|e| DocarooError::ParseError {
+            message: e.to_string(),
+            body: None,
+        })?;
+
+    for (index, request) in requests.iter().enumerate() {
+        request.validate().map_err(|e| match e {
+            DocarooError::InvalidRequest(message) => {
+                DocarooError::InvalidRequest(format!("Request {}: {}", index, message))
+            }
+            other => other,
+        })?;
+    }
+
+    Ok(requests)
+}
+
+/// Order floats from lowest to highest, with every NaN after every number
+///
+/// `f64::total_cmp` alone sorts negative NaN before every number, so a NaN could win
+/// a minimum; this keeps NaN last whatever its sign.
+pub(crate) fn cmp_asc_nan_last(a: f64, b: f64) -> std::cmp::Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (false, false) => a.total_cmp(&b),
+        (a_nan, b_nan) => a_nan.cmp(&b_nan),
+    }
+}
+
+/// Order floats from highest to lowest, with every NaN after every number
+pub(crate) fn cmp_desc_nan_last(a: f64, b: f64) -> std::cmp::Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (false, false) => b.total_cmp(&a),
+        (a_nan, b_nan) => a_nan.cmp(&b_nan),
+    }
+}
+
+/// Deserialize an NPI map, dropping NPIs whose value is `null`
+///
+/// The API sometimes sends `null` instead of `[]` or an object for an NPI it has no
+/// data on; treating those as absent keeps one empty entry from failing the response.
+fn deserialize_skip_null_npis<'de, D, V>(deserializer: D) -> std::result::Result<NpiMap<V>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    V: Deserialize<'de>,
+{
+    let data = NpiMap::<Option<V>>::deserialize(deserializer)?;
+    Ok(data
+        .into_iter()
+        .filter_map(|(npi, value)| Some((npi, value?)))
+        .collect())
+}
+
+/// Serialize NPIs in ascending order for stable request bodies and cache keys
+fn serialize_sorted_npis<S: serde::Serializer>(
+    npis: &[String],
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    let mut sorted: Vec<&String> = npis.iter().collect();
+    sorted.sort_unstable();
+    serializer.collect_seq(sorted)
+}
+
 /// Request for in-network pricing lookup
-#[derive(Debug, Clone, Serialize, Builder)]
+///
+/// Serializes to the API's JSON body and deserializes from it, so pending requests
+/// can be persisted and read back. [`tags`](Self::tags) are not part of the JSON and
+/// come back empty.
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
 #[serde(rename_all = "camelCase")]
 pub struct PricingRequest {
     /// List of National Provider Identifiers (NPIs) to lookup pricing for
     /// Must be 10-digit identifiers, 1-10 items allowed
+    ///
+    /// Serialized in ascending order, so requests for the same set of NPIs produce
+    /// identical bodies whatever order they were given in.
     #[builder(into)]
+    #[serde(serialize_with = "serialize_sorted_npis")]
     pub npis: Vec<String>,
     
     /// Medical billing code to retrieve pricing for
@@ -82,21 +501,411 @@ pub struct PricingRequest {
     pub condition_code: String,
     
     /// Insurance plan identifier (EIN, HIOS ID, or Custom Plan ID)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    ///
+    /// The builder takes a string or a [`PlanId`]; see
+    /// [`typed_plan_id`](Self::typed_plan_id) for the detected format.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     #[builder(into)]
     pub plan_id: Option<String>,
     
     /// Medical billing code standard
-    #[serde(skip_serializing_if = "Option::is_none")]
+    ///
+    /// When `None`, the field is left out of the request and the API applies its own
+    /// default, which is not necessarily [`CodeType::default()`]. Set
+    /// [`DocarooConfig::default_code_type`](crate::client::DocarooConfig::default_code_type)
+    /// to have the client fill it in instead.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub code_type: Option<CodeType>,
+
+    /// Caller-defined tags keyed by NPI, kept client-side and never sent to the API
+    ///
+    /// Use [`tag_npi`](Self::tag_npi) to attach your own identifiers to NPIs and
+    /// [`PricingResponse::with_tags`] to join them back onto the response.
+    #[serde(skip)]
+    #[builder(default)]
+    pub tags: HashMap<String, String>,
+}
+
+impl PricingRequest {
+    /// Check that the request satisfies the API's constraints
+    ///
+    /// Applies the same checks [`PricingClient::get_in_network_rates`] runs before
+    /// sending: 1 to [`MAX_NPIS_PER_PRICING_REQUEST`] NPIs, each passing
+    /// [`validate_npi`](crate::validation::validate_npi), and a non-empty condition code.
+    ///
+    /// [`PricingClient::get_in_network_rates`]: crate::pricing::PricingClient::get_in_network_rates
+    /// [`MAX_NPIS_PER_PRICING_REQUEST`]: crate::MAX_NPIS_PER_PRICING_REQUEST
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DocarooError::InvalidRequest`](crate::DocarooError::InvalidRequest)
+    /// describing the first violated constraint.
+    pub fn validate(&self) -> crate::Result<()> {
+        use crate::{MAX_NPIS_PER_PRICING_REQUEST, error::DocarooError};
+
+        // Validate NPIs count
+        if self.npis.is_empty() {
+            return Err(DocarooError::InvalidRequest(
+                "At least one NPI must be provided".to_string(),
+            ));
+        }
+
+        if self.npis.len() > MAX_NPIS_PER_PRICING_REQUEST {
+            return Err(DocarooError::InvalidRequest(format!(
+                "Maximum {} NPIs allowed per request",
+                MAX_NPIS_PER_PRICING_REQUEST
+            )));
+        }
+
+        // Validate NPI format and check digit
+        for npi in &self.npis {
+            crate::validation::validate_npi(npi)?;
+        }
+
+        // Validate condition code is not empty
+        if self.condition_code.trim().is_empty() {
+            return Err(DocarooError::InvalidRequest(
+                "Condition code cannot be empty".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Like [`validate`](Self::validate), but collect every violated constraint
+    ///
+    /// Returns one [`DocarooError::InvalidRequest`](crate::DocarooError::InvalidRequest)
+    /// per problem, in the order `validate` checks them: the NPI count, each invalid
+    /// NPI, then the condition code. `validate` stays the fail-fast check used when
+    /// sending; use this where all problems should be reported together.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use docaroo_rs::models::PricingRequest;
+    ///
+    /// let request = PricingRequest::builder()
+    ///     .npis(vec!["123".to_string(), "1234567890".to_string()])
+    ///     .condition_code(" ")
+    ///     .build();
+    ///
+    /// let errors = request.validate_all().unwrap_err();
+    /// assert_eq!(errors.len(), 3);
+    /// ```
+    pub fn validate_all(&self) -> std::result::Result<(), Vec<crate::DocarooError>> {
+        self.full_report().into_errors()
+    }
+
+    /// Check every constraint [`validate`](Self::validate) enforces without stopping at
+    /// the first failure
+    ///
+    /// Intended for forms that show all problems at once. Besides the checks
+    /// `validate` runs, the report flags a condition code that does not have the shape
+    /// of its declared code type (see
+    /// [`validate_code_format`](crate::validation::validate_code_format)).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use docaroo_rs::models::PricingRequest;
+    ///
+    /// let request = PricingRequest::builder()
+    ///     .npis(vec!["1043566623".to_string(), "123".to_string()])
+    ///     .condition_code("")
+    ///     .build();
+    ///
+    /// let report = request.full_report();
+    /// assert!(!report.is_valid());
+    /// assert_eq!(report.npi_errors.len(), 1);
+    /// assert!(report.code_error.is_some());
+    /// ```
+    pub fn full_report(&self) -> ValidationReport {
+        ValidationReport::check(
+            &self.npis,
+            crate::MAX_NPIS_PER_PRICING_REQUEST,
+            &self.condition_code,
+            self.code_type,
+        )
+    }
+
+    /// Check the request for likely mistakes that the API will not reject
+    ///
+    /// Returns human-readable advisories; an empty list means nothing looked off.
+    /// Classifies NPIs with [`npi_entity_type`], which cannot tell entity types apart;
+    /// use [`lint_with`](Self::lint_with) to supply them from NPPES.
+    pub fn lint(&self) -> Vec<String> {
+        self.lint_with(npi_entity_type)
+    }
+
+    /// Check the request for likely mistakes, using caller-supplied entity types
+    ///
+    /// `entity_type` returns the entity type of an NPI, or `None` if it is unknown.
+    /// Flags requests mixing individual and organizational NPIs, whose rates are
+    /// rarely comparable; NPIs of unknown type are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use docaroo_rs::models::{NpiEntityType, PricingRequest};
+    /// use std::collections::HashMap;
+    ///
+    /// // e.g. loaded from an NPPES download
+    /// let registry = HashMap::from([
+    ///     ("1043566623", NpiEntityType::Individual),
+    ///     ("1972767655", NpiEntityType::Organization),
+    /// ]);
+    ///
+    /// let request = PricingRequest::builder()
+    ///     .npis(vec!["1043566623".to_string(), "1972767655".to_string()])
+    ///     .condition_code("99214")
+    ///     .build();
+    ///
+    /// let warnings = request.lint_with(|npi| registry.get(npi).copied());
+    /// assert_eq!(warnings.len(), 1);
+    /// ```
+    pub fn lint_with(&self, entity_type: impl Fn(&str) -> Option<NpiEntityType>) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        let entity_types: Vec<NpiEntityType> =
+            self.npis.iter().filter_map(|npi| entity_type(npi)).collect();
+        if entity_types.contains(&NpiEntityType::Individual)
+            && entity_types.contains(&NpiEntityType::Organization)
+        {
+            warnings.push(
+                "Request mixes individual and organizational NPIs; their rates may not be comparable"
+                    .to_string(),
+            );
+        }
+
+        warnings
+    }
+
+    /// Clone this request with a different set of NPIs
+    pub fn with_npis(&self, npis: impl Into<Vec<String>>) -> Self {
+        let mut request = self.clone();
+        request.npis = npis.into();
+        request
+    }
+
+    /// Clone this request with a different condition code
+    pub fn with_condition_code(&self, condition_code: impl Into<String>) -> Self {
+        let mut request = self.clone();
+        request.condition_code = condition_code.into();
+        request
+    }
+
+    /// The plan identifier classified by format, if one is set
+    pub fn typed_plan_id(&self) -> Option<PlanId> {
+        self.plan_id.as_deref().map(PlanId::from)
+    }
+
+    /// Clone this request with a different plan identifier
+    pub fn with_plan_id(&self, plan_id: impl Into<String>) -> Self {
+        let mut request = self.clone();
+        request.plan_id = Some(plan_id.into());
+        request
+    }
+
+    /// Clone this request with a different code type
+    pub fn with_code_type(&self, code_type: CodeType) -> Self {
+        let mut request = self.clone();
+        request.code_type = Some(code_type);
+        request
+    }
+
+    /// Attach a caller-defined tag, such as an internal provider ID, to an NPI
+    ///
+    /// Tags stay on the client; see [`PricingResponse::with_tags`].
+    pub fn tag_npi(&mut self, npi: impl Into<String>, tag: impl Into<String>) {
+        self.tags.insert(npi.into(), tag.into());
+    }
+}
+
+/// Convert integer NPIs to their zero-padded 10-digit string form, checking each one
+/// with [`validate_npi`](crate::validation::validate_npi)
+fn npis_from_ints(npis: impl IntoIterator<Item = u64>) -> crate::Result<Vec<String>> {
+    npis.into_iter()
+        .map(|npi| {
+            if npi > 9_999_999_999 {
+                return Err(crate::error::DocarooError::InvalidRequest(format!(
+                    "Invalid NPI: {} has more than 10 digits",
+                    npi
+                )));
+            }
+            let npi = format!("{:010}", npi);
+            crate::validation::validate_npi(&npi)?;
+            Ok(npi)
+        })
+        .collect()
+}
+
+impl<S: pricing_request_builder::State> PricingRequestBuilder<S> {
+    /// Set the NPIs from integers, zero-padding each to 10 digits
+    ///
+    /// # Errors
+    ///
+    /// Returns `DocarooError::InvalidRequest` if any value has more than 10 digits or
+    /// fails the NPI check digit
+    pub fn npis_from_ints(
+        self,
+        npis: impl IntoIterator<Item = u64>,
+    ) -> crate::Result<PricingRequestBuilder<pricing_request_builder::SetNpis<S>>>
+    where
+        S::Npis: pricing_request_builder::IsUnset,
+    {
+        Ok(self.npis(npis_from_ints(npis)?))
+    }
+
+    /// Set the NPIs from already-validated [`Npi`]s
+    pub fn typed_npis(
+        self,
+        npis: impl IntoIterator<Item = Npi>,
+    ) -> PricingRequestBuilder<pricing_request_builder::SetNpis<S>>
+    where
+        S::Npis: pricing_request_builder::IsUnset,
+    {
+        self.npis(npis.into_iter().map(Npi::into_inner).collect::<Vec<_>>())
+    }
+}
+
+impl<S: pricing_request_builder::IsComplete> PricingRequestBuilder<S> {
+    /// Build the request and check it with [`PricingRequest::validate`]
+    ///
+    /// A fallible alternative to `build()` for catching invalid requests where they
+    /// are constructed, without a client.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DocarooError::InvalidRequest`](crate::DocarooError::InvalidRequest)
+    /// describing the first violated constraint.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use docaroo_rs::models::PricingRequest;
+    ///
+    /// let result = PricingRequest::builder()
+    ///     .npis(Vec::<String>::new())
+    ///     .condition_code("99214")
+    ///     .try_build();
+    /// assert!(result.is_err());
+    /// ```
+    pub fn try_build(self) -> crate::Result<PricingRequest> {
+        let request = self.build();
+        request.validate()?;
+        Ok(request)
+    }
+}
+
+/// Every validation problem found in a request
+///
+/// Returned by [`PricingRequest::full_report`] and [`LikelihoodRequest::full_report`].
+/// Each field holds a human-readable message; an empty report means the request is
+/// valid.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// Position and problem of each malformed NPI, in request order
+    pub npi_errors: Vec<(usize, String)>,
+    /// Problem with the condition code
+    pub code_error: Option<String>,
+    /// Mismatch between the condition code and its declared code type
+    pub code_type_error: Option<String>,
+    /// Problem with the number of NPIs
+    pub count_error: Option<String>,
 }
 
+impl ValidationReport {
+    /// Check a request's fields, collecting every problem
+    fn check(
+        npis: &[String],
+        max_npis: usize,
+        condition_code: &str,
+        code_type: Option<CodeType>,
+    ) -> Self {
+        use crate::error::DocarooError;
+
+        let count_error = if npis.is_empty() {
+            Some("At least one NPI must be provided".to_string())
+        } else if npis.len() > max_npis {
+            Some(format!("Maximum {} NPIs allowed per request", max_npis))
+        } else {
+            None
+        };
+
+        let npi_errors = npis
+            .iter()
+            .enumerate()
+            .filter_map(|(index, npi)| match crate::validation::validate_npi(npi) {
+                Ok(()) => None,
+                Err(DocarooError::InvalidRequest(message)) => Some((index, message)),
+                Err(error) => Some((index, error.to_string())),
+            })
+            .collect();
+
+        let code = condition_code.trim();
+        let code_error =
+            code.is_empty().then(|| "Condition code cannot be empty".to_string());
+        let code_type_error = match code_type {
+            Some(code_type) if !code.is_empty() => {
+                match crate::validation::validate_code_format(code, code_type) {
+                    Ok(()) => None,
+                    Err(DocarooError::InvalidRequest(message)) => Some(message),
+                    Err(error) => Some(error.to_string()),
+                }
+            }
+            _ => None,
+        };
+
+        Self {
+            npi_errors,
+            code_error,
+            code_type_error,
+            count_error,
+        }
+    }
+
+    /// Convert the problems [`PricingRequest::validate`] also checks into errors
+    ///
+    /// `code_type_error` is left out, since `validate` only checks the code's format
+    /// when a client enables strict code validation.
+    fn into_errors(self) -> std::result::Result<(), Vec<crate::DocarooError>> {
+        use crate::error::DocarooError;
+
+        let errors: Vec<DocarooError> = self
+            .count_error
+            .into_iter()
+            .chain(self.npi_errors.into_iter().map(|(_, message)| message))
+            .chain(self.code_error)
+            .map(DocarooError::InvalidRequest)
+            .collect();
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
+    }
+
+    /// Check whether no problems were found
+    pub fn is_valid(&self) -> bool {
+        self.npi_errors.is_empty()
+            && self.code_error.is_none()
+            && self.code_type_error.is_none()
+            && self.count_error.is_none()
+    }
+}
+
+/// A single pricing query within a batch
+///
+/// See [`DocarooClient::batch`](crate::DocarooClient::batch).
+pub type PricingQuery = PricingRequest;
+
 /// Request for procedure likelihood evaluation
-#[derive(Debug, Clone, Serialize, Builder)]
+///
+/// Round-trips through JSON like [`PricingRequest`].
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
 #[serde(rename_all = "camelCase")]
 pub struct LikelihoodRequest {
     /// List of National Provider Identifiers (NPIs) to evaluate
+    ///
+    /// Serialized in ascending order, like [`PricingRequest::npis`].
     #[builder(into)]
+    #[serde(serialize_with = "serialize_sorted_npis")]
     pub npis: Vec<String>,
     
     /// Medical billing code to evaluate likelihood for
@@ -104,30 +913,695 @@ pub struct LikelihoodRequest {
     pub condition_code: String,
     
     /// Medical billing code standard
+    ///
+    /// Set a code type given by name with
+    /// [`try_code_type`](LikelihoodRequestBuilder::try_code_type).
     #[builder(into)]
-    pub code_type: String,
+    pub code_type: CodeType,
+}
+
+impl LikelihoodRequest {
+    /// Check that the request satisfies the API's constraints
+    ///
+    /// Applies the checks [`ProceduresClient::get_likelihood`] runs before sending:
+    /// 1 to [`MAX_NPIS_PER_LIKELIHOOD_REQUEST`] NPIs, each passing
+    /// [`validate_npi`](crate::validation::validate_npi), and a non-empty condition
+    /// code. A client configured with a different `max_likelihood_npis` applies its own
+    /// limit instead.
+    ///
+    /// [`ProceduresClient::get_likelihood`]: crate::procedures::ProceduresClient::get_likelihood
+    /// [`MAX_NPIS_PER_LIKELIHOOD_REQUEST`]: crate::MAX_NPIS_PER_LIKELIHOOD_REQUEST
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DocarooError::InvalidRequest`](crate::DocarooError::InvalidRequest)
+    /// describing the first violated constraint.
+    pub fn validate(&self) -> crate::Result<()> {
+        self.validate_with_max_npis(crate::MAX_NPIS_PER_LIKELIHOOD_REQUEST)
+    }
+
+    /// [`validate`](Self::validate) with a custom NPI limit
+    pub(crate) fn validate_with_max_npis(&self, max_npis: usize) -> crate::Result<()> {
+        use crate::error::DocarooError;
+
+        if self.npis.is_empty() {
+            return Err(DocarooError::InvalidRequest(
+                "At least one NPI must be provided".to_string(),
+            ));
+        }
+
+        if self.npis.len() > max_npis {
+            return Err(DocarooError::InvalidRequest(format!(
+                "Maximum {} NPIs allowed per request",
+                max_npis
+            )));
+        }
+
+        for npi in &self.npis {
+            crate::validation::validate_npi(npi)?;
+        }
+
+        if self.condition_code.trim().is_empty() {
+            return Err(DocarooError::InvalidRequest(
+                "Condition code cannot be empty".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Like [`validate`](Self::validate), but collect every violated constraint
+    ///
+    /// See [`PricingRequest::validate_all`].
+    pub fn validate_all(&self) -> std::result::Result<(), Vec<crate::DocarooError>> {
+        self.full_report().into_errors()
+    }
+
+    /// Check the request without stopping at the first problem
+    ///
+    /// Like [`PricingRequest::full_report`], with the NPI count limited to
+    /// [`MAX_NPIS_PER_LIKELIHOOD_REQUEST`](crate::MAX_NPIS_PER_LIKELIHOOD_REQUEST);
+    /// a client configured with a different `max_likelihood_npis` applies its own
+    /// limit when sending.
+    pub fn full_report(&self) -> ValidationReport {
+        ValidationReport::check(
+            &self.npis,
+            crate::MAX_NPIS_PER_LIKELIHOOD_REQUEST,
+            &self.condition_code,
+            Some(self.code_type),
+        )
+    }
+
+    /// Clone this request with a different set of NPIs
+    pub fn with_npis(&self, npis: impl Into<Vec<String>>) -> Self {
+        let mut request = self.clone();
+        request.npis = npis.into();
+        request
+    }
+
+    /// Clone this request with a different condition code
+    pub fn with_condition_code(&self, condition_code: impl Into<String>) -> Self {
+        let mut request = self.clone();
+        request.condition_code = condition_code.into();
+        request
+    }
+
+    /// Clone this request with a different code type
+    pub fn with_code_type(&self, code_type: impl Into<CodeType>) -> Self {
+        let mut request = self.clone();
+        request.code_type = code_type.into();
+        request
+    }
+}
+
+impl<S: likelihood_request_builder::State> LikelihoodRequestBuilder<S> {
+    /// Set the NPIs from integers, zero-padding each to 10 digits
+    ///
+    /// # Errors
+    ///
+    /// Returns `DocarooError::InvalidRequest` if any value has more than 10 digits or
+    /// fails the NPI check digit
+    pub fn npis_from_ints(
+        self,
+        npis: impl IntoIterator<Item = u64>,
+    ) -> crate::Result<LikelihoodRequestBuilder<likelihood_request_builder::SetNpis<S>>>
+    where
+        S::Npis: likelihood_request_builder::IsUnset,
+    {
+        Ok(self.npis(npis_from_ints(npis)?))
+    }
+
+    /// Set the NPIs from already-validated [`Npi`]s
+    pub fn typed_npis(
+        self,
+        npis: impl IntoIterator<Item = Npi>,
+    ) -> LikelihoodRequestBuilder<likelihood_request_builder::SetNpis<S>>
+    where
+        S::Npis: likelihood_request_builder::IsUnset,
+    {
+        self.npis(npis.into_iter().map(Npi::into_inner).collect::<Vec<_>>())
+    }
+
+    /// Set the code type from its wire name, e.g. `"CPT"`, ignoring case
+    ///
+    /// # Errors
+    ///
+    /// Returns `DocarooError::InvalidRequest` if `code_type` is not the name of a code
+    /// type
+    pub fn try_code_type(
+        self,
+        code_type: &str,
+    ) -> crate::Result<LikelihoodRequestBuilder<likelihood_request_builder::SetCodeType<S>>>
+    where
+        S::CodeType: likelihood_request_builder::IsUnset,
+    {
+        Ok(self.code_type(code_type.parse::<CodeType>()?))
+    }
+}
+
+impl<S: likelihood_request_builder::IsComplete> LikelihoodRequestBuilder<S> {
+    /// Build the request and check it with [`LikelihoodRequest::validate`]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DocarooError::InvalidRequest`](crate::DocarooError::InvalidRequest)
+    /// describing the first violated constraint.
+    pub fn try_build(self) -> crate::Result<LikelihoodRequest> {
+        let request = self.build();
+        request.validate()?;
+        Ok(request)
+    }
+}
+
+/// How [`PricingResponse::merge_all`] resolves conflicting rates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConflictStrategy {
+    /// Keep the rate from the earliest response
+    #[default]
+    KeepFirst,
+    /// Keep the rate backed by the most instances (the earliest wins ties)
+    KeepHighestInstances,
+    /// Average `min_rate`, `max_rate` and `avg_rate` and sum `instances`
+    ///
+    /// The averages are unweighted: each response counts once, however many instances
+    /// back its rate. The summed `instances` saturate at `u32::MAX`.
+    Average,
 }
 
 /// Response containing pricing data
-#[derive(Debug, Clone, Deserialize)]
+///
+/// Equality compares every field, including client-side ones such as
+/// `requested_plan_id`. Rates are compared with `==`, so they must match exactly and
+/// a NaN rate is never equal to anything; see [`RateData::approx_eq`] for comparing
+/// computed rates.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct PricingResponse {
     /// Pricing data organized by NPI
-    pub data: HashMap<String, Vec<RateData>>,
+    ///
+    /// NPIs the API returned `null` for are left out, as if they were absent.
+    #[serde(deserialize_with = "deserialize_skip_null_npis")]
+    pub data: NpiMap<Vec<RateData>>,
     /// Response metadata
     pub meta: PricingMeta,
+    /// Plan identifier supplied on the originating request, if any
+    ///
+    /// Populated by [`PricingClient`](crate::pricing::PricingClient); not part of the
+    /// wire format.
+    #[serde(skip)]
+    pub requested_plan_id: Option<String>,
+    /// Parse error for each NPI whose rates could not be deserialized, keyed by NPI
+    ///
+    /// Only populated when
+    /// [`DocarooConfig::lenient_parsing`](crate::client::DocarooConfig) is set; those
+    /// NPIs are left out of `data`. Not part of the wire format.
+    #[serde(skip)]
+    pub parse_errors: HashMap<String, String>,
+    /// Top-level response fields this crate does not model yet, keyed by field name
+    ///
+    /// Kept so that fields the API adds are not lost, and written back out when the
+    /// response is serialized.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// Pricing response whose rates are kept as raw JSON until parsed NPI by NPI
+#[derive(Deserialize)]
+pub(crate) struct LenientPricingResponse {
+    #[serde(deserialize_with = "deserialize_skip_null_npis")]
+    data: NpiMap<serde_json::Value>,
+    meta: PricingMeta,
+    #[serde(flatten)]
+    extra: HashMap<String, serde_json::Value>,
+}
+
+impl From<LenientPricingResponse> for PricingResponse {
+    fn from(lenient: LenientPricingResponse) -> Self {
+        let mut data = NpiMap::default();
+        let mut parse_errors = HashMap::new();
+        for (npi, rates) in lenient.data {
+            match serde_json::from_value::<Vec<RateData>>(rates) {
+                Ok(rates) => {
+                    data.insert(npi, rates);
+                }
+                Err(error) => {
+                    parse_errors.insert(npi, error.to_string());
+                }
+            }
+        }
+
+        PricingResponse {
+            data,
+            meta: lenient.meta,
+            requested_plan_id: None,
+            parse_errors,
+            extra: lenient.extra,
+        }
+    }
+}
+
+impl PricingResponse {
+    /// Rates returned for an NPI, or `None` if the response has no entry for it
+    ///
+    /// Unlike indexing `data` directly, a missing NPI does not panic.
+    pub fn rates_for(&self, npi: &str) -> Option<&[RateData]> {
+        self.data.get(npi).map(Vec::as_slice)
+    }
+
+    /// NPIs present in the response
+    pub fn npis(&self) -> impl Iterator<Item = &str> {
+        self.data.keys().map(String::as_str)
+    }
+
+    /// Compute instance-weighted percentiles of `avg_rate` across every returned rate
+    ///
+    /// Each rate counts `instances` times, as if the data were expanded into one value
+    /// per instance. Percentiles use the nearest-rank method on that expanded data:
+    /// for a percentile `p` (in `0.0..=100.0`) over `N` total instances, the result is
+    /// the smallest `avg_rate` whose cumulative instance count reaches
+    /// `ceil(p / 100 * N)` (at least 1). Values outside `0..=100` are clamped.
+    ///
+    /// Rates with zero instances or a NaN `avg_rate` are ignored. Returns one value per
+    /// requested percentile, or an empty `Vec` if there is no weighted data.
+    pub fn weighted_percentiles(&self, ps: &[f64]) -> Vec<f64> {
+        let mut weighted: Vec<(f64, u64)> = self
+            .data
+            .values()
+            .flatten()
+            .filter(|rate| rate.instances > 0 && !rate.avg_rate.is_nan())
+            .map(|rate| (rate.avg_rate, u64::from(rate.instances)))
+            .collect();
+        if weighted.is_empty() {
+            return Vec::new();
+        }
+        weighted.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let total: u64 = weighted.iter().map(|(_, weight)| weight).sum();
+
+        ps.iter()
+            .map(|p| {
+                let p = p.clamp(0.0, 100.0);
+                let rank = ((p / 100.0 * total as f64).ceil() as u64).max(1);
+
+                let mut cumulative = 0;
+                weighted
+                    .iter()
+                    .find(|(_, weight)| {
+                        cumulative += weight;
+                        cumulative >= rank
+                    })
+                    .map(|(value, _)| *value)
+                    .unwrap_or(weighted[weighted.len() - 1].0)
+            })
+            .collect()
+    }
+
+    /// Select the rate backed by the most instances for each NPI
+    ///
+    /// Ties on `instances` go to the lowest `avg_rate`, with a NaN `avg_rate` losing
+    /// every tie. NPIs without any rates are omitted; the rest keep their order in
+    /// [`data`](Self::data).
+    pub fn best_rate_per_npi(&self) -> NpiMap<&RateData> {
+        self.data
+            .iter()
+            .filter_map(|(npi, rates)| {
+                rates
+                    .iter()
+                    .max_by(|a, b| {
+                        a.instances
+                            .cmp(&b.instances)
+                            .then_with(|| cmp_asc_nan_last(b.avg_rate, a.avg_rate))
+                    })
+                    .map(|rate| (npi.clone(), rate))
+            })
+            .collect()
+    }
+
+    /// Check that this response answers the given request
+    ///
+    /// Every NPI in the response must have been requested, and every rate must be for
+    /// the requested condition code. NPIs that were requested but not returned are not
+    /// an error, since the API omits providers without rates.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DocarooError::InvalidRequest` describing the first mismatch found
+    pub fn validate_against(&self, request: &PricingRequest) -> crate::Result<()> {
+        use crate::error::DocarooError;
+
+        let mut npis: Vec<&String> = self.data.keys().collect();
+        npis.sort();
+
+        for npi in npis {
+            if !request.npis.contains(npi) {
+                return Err(DocarooError::InvalidRequest(format!(
+                    "Response contains NPI '{}' which was not requested",
+                    npi
+                )));
+            }
+
+            if let Some(rate) = self.data[npi]
+                .iter()
+                .find(|rate| rate.code != request.condition_code)
+            {
+                return Err(DocarooError::InvalidRequest(format!(
+                    "Response for NPI '{}' contains code '{}' but '{}' was requested",
+                    npi, rate.code, request.condition_code
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether the server chose the plan reported in `meta.plan_id`
+    ///
+    /// Returns `true` when the originating request did not specify a `plan_id`.
+    pub fn plan_was_defaulted(&self) -> bool {
+        self.requested_plan_id.is_none()
+    }
+
+    /// Join the tags stored on the originating request back onto this response's rates
+    ///
+    /// Returns `(tag, rates)` keyed by NPI for every NPI that was tagged with
+    /// [`PricingRequest::tag_npi`] and has rate data. Untagged NPIs are left out.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use docaroo_rs::{DocarooClient, models::PricingRequest};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DocarooClient::new("your-api-key");
+    ///
+    /// let mut request = PricingRequest::builder()
+    ///     .npis(vec!["1043566623".to_string()])
+    ///     .condition_code("99214")
+    ///     .build();
+    /// request.tag_npi("1043566623", "provider-0042");
+    ///
+    /// let response = client.pricing().get_in_network_rates(request.clone()).await?;
+    /// for (npi, (tag, rates)) in response.with_tags(&request) {
+    ///     println!("{} ({}): {} rates", tag, npi, rates.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tags(&self, request: &PricingRequest) -> HashMap<String, (String, Vec<RateData>)> {
+        self.data
+            .iter()
+            .filter_map(|(npi, rates)| {
+                let tag = request.tags.get(npi)?;
+                Some((npi.clone(), (tag.clone(), rates.clone())))
+            })
+            .collect()
+    }
+
+    /// Copy of this response that is safe to share in bug reports
+    ///
+    /// NPI keys are replaced by `NPI_1`, `NPI_2`, ... in ascending NPI order, and the
+    /// plan ID and payer are blanked (including the requested plan ID, if any). Rate
+    /// data and the remaining metadata are kept as-is.
+    pub fn anonymized(&self) -> PricingResponse {
+        let mut npis: Vec<&String> = self.data.keys().collect();
+        npis.sort();
+
+        let data = npis
+            .into_iter()
+            .enumerate()
+            .map(|(index, npi)| (format!("NPI_{}", index + 1), self.data[npi].clone()))
+            .collect();
+
+        let mut meta = self.meta.clone();
+        meta.plan_id = String::new();
+        meta.payer = Payer::new("");
+
+        PricingResponse {
+            data,
+            meta,
+            requested_plan_id: self.requested_plan_id.as_ref().map(|_| String::new()),
+            // Keyed by the real NPIs, so dropped along with them
+            parse_errors: HashMap::new(),
+            extra: self.extra.clone(),
+        }
+    }
+
+    /// Split a multi-NPI response into one response per NPI
+    ///
+    /// Each returned response holds only that NPI's rates, with the metadata, requested
+    /// plan and extra fields cloned from the original. Pairs are returned in the iteration
+    /// order of [`NpiMap`].
+    pub fn split_by_npi(self) -> Vec<(String, PricingResponse)> {
+        let meta = self.meta;
+        let requested_plan_id = self.requested_plan_id;
+        let extra = self.extra;
+
+        self.data
+            .into_iter()
+            .map(|(npi, rates)| {
+                let response = PricingResponse {
+                    data: NpiMap::from_iter([(npi.clone(), rates)]),
+                    meta: meta.clone(),
+                    requested_plan_id: requested_plan_id.clone(),
+                    parse_errors: HashMap::new(),
+                    extra: extra.clone(),
+                };
+                (npi, response)
+            })
+            .collect()
+    }
+
+    /// Merge responses split across several requests into one
+    ///
+    /// Rates for each NPI are concatenated in response order. Two rates conflict when
+    /// they share an NPI, code, code type, negotiated type and modifier; each set of
+    /// conflicting rates is collapsed into one according to `conflict`. The merged
+    /// response takes its metadata, requested plan and extra fields from the first
+    /// response, and collects every response's parse errors. It is empty if
+    /// `responses` is.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use docaroo_rs::models::{ConflictStrategy, PricingResponse};
+    /// # fn example(pages: Vec<PricingResponse>) {
+    /// let merged = PricingResponse::merge_all(pages, ConflictStrategy::KeepHighestInstances);
+    /// println!("{} providers", merged.data.len());
+    /// # }
+    /// ```
+    pub fn merge_all(responses: Vec<PricingResponse>, conflict: ConflictStrategy) -> PricingResponse {
+        type RateKey = (String, String, String, Option<String>);
+        type RateGroups = (Vec<RateKey>, HashMap<RateKey, Vec<RateData>>);
+
+        let mut responses = responses.into_iter();
+        let Some(first) = responses.next() else {
+            return PricingResponse::default();
+        };
+
+        let meta = first.meta.clone();
+        let requested_plan_id = first.requested_plan_id.clone();
+        let extra = first.extra.clone();
+
+        // Collect every rate per NPI under its conflict key, remembering first-seen order
+        let mut merged: NpiMap<RateGroups> = NpiMap::default();
+        let mut parse_errors = HashMap::new();
+        for response in std::iter::once(first).chain(responses) {
+            parse_errors.extend(response.parse_errors);
+            for (npi, rates) in response.data {
+                let (order, groups) = merged.entry(npi).or_default();
+                for rate in rates {
+                    let key = (
+                        rate.code.clone(),
+                        rate.code_type.clone(),
+                        rate.negotiated_type.clone(),
+                        rate.modifier.clone(),
+                    );
+                    let group = groups.entry(key.clone()).or_default();
+                    if group.is_empty() {
+                        order.push(key);
+                    }
+                    group.push(rate);
+                }
+            }
+        }
+
+        let data = merged
+            .into_iter()
+            .map(|(npi, (order, mut groups))| {
+                let rates = order
+                    .into_iter()
+                    .filter_map(|key| groups.remove(&key))
+                    .filter_map(|group| Self::resolve_conflict(group, conflict))
+                    .collect();
+                (npi, rates)
+            })
+            .collect();
+
+        PricingResponse {
+            data,
+            meta,
+            requested_plan_id,
+            parse_errors,
+            extra,
+        }
+    }
+
+    /// Collapse a set of conflicting rates (in response order) into one
+    fn resolve_conflict(group: Vec<RateData>, conflict: ConflictStrategy) -> Option<RateData> {
+        match conflict {
+            ConflictStrategy::KeepFirst => group.into_iter().next(),
+            ConflictStrategy::KeepHighestInstances => {
+                group.into_iter().reduce(|best, rate| {
+                    if rate.instances > best.instances { rate } else { best }
+                })
+            }
+            ConflictStrategy::Average => {
+                let count = group.len() as f64;
+                let mut group = group.into_iter();
+                let mut merged = group.next()?;
+                for rate in group {
+                    merged.min_rate += rate.min_rate;
+                    merged.max_rate += rate.max_rate;
+                    merged.avg_rate += rate.avg_rate;
+                    merged.instances = merged.instances.saturating_add(rate.instances);
+                }
+                merged.min_rate /= count;
+                merged.max_rate /= count;
+                merged.avg_rate /= count;
+                Some(merged)
+            }
+        }
+    }
+
+    /// Total number of rate instances across every rate in the response
+    ///
+    /// Summed as `u64` so large responses cannot overflow.
+    pub fn total_instances(&self) -> u64 {
+        self.data
+            .values()
+            .flatten()
+            .map(|rate| u64::from(rate.instances))
+            .sum()
+    }
+
+    /// The NPI with the lowest `avg_rate`, with that rate
+    ///
+    /// Each NPI is represented by its cheapest rate. Ties go to the lowest NPI, rates
+    /// with a NaN `avg_rate` are ignored, and `None` is returned if there are no rates.
+    pub fn cheapest_npi(&self) -> Option<(&str, &RateData)> {
+        self.rates_by_avg()
+            .min_by(|a, b| a.1.avg_rate.total_cmp(&b.1.avg_rate).then_with(|| a.0.cmp(b.0)))
+    }
+
+    /// The NPI with the highest `avg_rate`, with that rate
+    ///
+    /// Each NPI is represented by its most expensive rate. Ties go to the lowest NPI,
+    /// rates with a NaN `avg_rate` are ignored, and `None` is returned if there are no
+    /// rates.
+    pub fn most_expensive_npi(&self) -> Option<(&str, &RateData)> {
+        self.rates_by_avg()
+            .max_by(|a, b| a.1.avg_rate.total_cmp(&b.1.avg_rate).then_with(|| b.0.cmp(a.0)))
+    }
+
+    /// Unweighted mean of `avg_rate` across every rate in the response
+    ///
+    /// Rates with a NaN `avg_rate` are ignored; returns `None` if no rates remain.
+    pub fn overall_avg_rate(&self) -> Option<f64> {
+        let (sum, count) = self
+            .data
+            .values()
+            .flatten()
+            .filter(|rate| !rate.avg_rate.is_nan())
+            .fold((0.0, 0usize), |(sum, count), rate| (sum + rate.avg_rate, count + 1));
+        (count > 0).then(|| sum / count as f64)
+    }
+
+    /// Every (NPI, rate) pair with a comparable `avg_rate`
+    fn rates_by_avg(&self) -> impl Iterator<Item = (&str, &RateData)> {
+        self.data.iter().flat_map(|(npi, rates)| {
+            rates
+                .iter()
+                .filter(|rate| !rate.avg_rate.is_nan())
+                .map(move |rate| (npi.as_str(), rate))
+        })
+    }
+
+    /// Whether the metadata reports matching records but no rate data came back
+    ///
+    /// A positive `in_network_records_count` with empty `data` usually means the
+    /// server filtered out results it should have returned.
+    pub fn is_suspicious(&self) -> bool {
+        self.meta.in_network_records_count > 0 && self.data.is_empty()
+    }
+
+    /// Write the pricing data as CSV
+    ///
+    /// Emits a header row followed by one row per (NPI, rate) pair containing every
+    /// `RateData` field plus the response `request_id` and `payer`. NPIs are written
+    /// in ascending order so the output is stable across runs.
+    #[cfg(feature = "csv")]
+    pub fn write_csv<W: std::io::Write>(&self, writer: W) -> std::io::Result<()> {
+        let mut csv_writer = csv::Writer::from_writer(writer);
+        csv_writer.write_record([
+            "npi",
+            "code",
+            "code_type",
+            "negotiated_type",
+            "min_rate",
+            "max_rate",
+            "avg_rate",
+            "instances",
+            "modifier",
+            "request_id",
+            "payer",
+        ])?;
+
+        let mut npis: Vec<&String> = self.data.keys().collect();
+        npis.sort();
+
+        for npi in npis {
+            for rate in &self.data[npi] {
+                csv_writer.write_record([
+                    npi.as_str(),
+                    &rate.code,
+                    &rate.code_type,
+                    &rate.negotiated_type,
+                    &rate.min_rate.to_string(),
+                    &rate.max_rate.to_string(),
+                    &rate.avg_rate.to_string(),
+                    &rate.instances.to_string(),
+                    rate.modifier.as_deref().unwrap_or_default(),
+                    &self.meta.request_id,
+                    &self.meta.payer,
+                ])?;
+            }
+        }
+
+        csv_writer.flush()
+    }
 }
 
 /// Response containing likelihood scores
-#[derive(Debug, Clone, Deserialize)]
+///
+/// Equality compares scores with `==`, so they must match exactly and a NaN score is
+/// never equal to anything.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LikelihoodResponse {
     /// Likelihood scores organized by NPI
-    pub data: HashMap<String, LikelihoodData>,
+    ///
+    /// NPIs the API returned `null` for are left out, as if they were absent.
+    #[serde(deserialize_with = "deserialize_skip_null_npis")]
+    pub data: NpiMap<LikelihoodData>,
     /// Response metadata
     pub meta: LikelihoodMeta,
+    /// Top-level response fields this crate does not model yet, keyed by field name
+    ///
+    /// Kept so that fields the API adds are not lost, and written back out when the
+    /// response is serialized.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// Rate data for a specific billing code
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RateData {
     /// Medical billing code
@@ -144,10 +1618,72 @@ pub struct RateData {
     pub avg_rate: f64,
     /// Number of rate instances found
     pub instances: u32,
+    /// Billing modifier the rate applies to (e.g. `25`), if modifier-specific
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modifier: Option<String>,
+}
+
+impl RateData {
+    /// The billing code including its modifier, e.g. `99214-25`
+    ///
+    /// Returns just the code when the rate has no modifier.
+    pub fn full_code(&self) -> String {
+        match &self.modifier {
+            Some(modifier) => format!("{}-{}", self.code, modifier),
+            None => self.code.clone(),
+        }
+    }
+
+    /// Smoothing constant used by [`per_instance_confidence_weight`](Self::per_instance_confidence_weight)
+    ///
+    /// A rate backed by this many instances gets a weight of exactly 0.5.
+    pub const CONFIDENCE_SMOOTHING: f64 = 10.0;
+
+    /// Confidence weight between 0 and 1 based on how many instances back this rate
+    ///
+    /// Computed as `instances / (instances + k)` with `k` =
+    /// [`CONFIDENCE_SMOOTHING`](Self::CONFIDENCE_SMOOTHING): 0 for no instances, rising
+    /// monotonically toward 1 as instances grow. Useful as a weight when aggregating
+    /// rates across providers, or alongside a likelihood score.
+    pub fn per_instance_confidence_weight(&self) -> f64 {
+        let instances = f64::from(self.instances);
+        instances / (instances + Self::CONFIDENCE_SMOOTHING)
+    }
+
+    /// Tolerance used by [`is_consistent`](Self::is_consistent) to absorb rounding
+    pub const RATE_EPSILON: f64 = 0.01;
+
+    /// Check that the average rate lies between the minimum and maximum rates
+    ///
+    /// Allows [`RATE_EPSILON`](Self::RATE_EPSILON) of slack on either side. Returns
+    /// `false` if any of the rates is NaN.
+    pub fn is_consistent(&self) -> bool {
+        self.min_rate <= self.max_rate + Self::RATE_EPSILON
+            && self.avg_rate >= self.min_rate - Self::RATE_EPSILON
+            && self.avg_rate <= self.max_rate + Self::RATE_EPSILON
+    }
+
+    /// Compare two rates, allowing the minimum, maximum and average to differ by up
+    /// to `epsilon`
+    ///
+    /// Every other field must be equal. Useful where `==` is too strict, e.g. for
+    /// rates computed by averaging. Returns `false` if either side has a NaN rate.
+    pub fn approx_eq(&self, other: &RateData, epsilon: f64) -> bool {
+        let close = |a: f64, b: f64| (a - b).abs() <= epsilon;
+
+        self.code == other.code
+            && self.code_type == other.code_type
+            && self.negotiated_type == other.negotiated_type
+            && self.instances == other.instances
+            && self.modifier == other.modifier
+            && close(self.min_rate, other.min_rate)
+            && close(self.max_rate, other.max_rate)
+            && close(self.avg_rate, other.avg_rate)
+    }
 }
 
 /// Likelihood data for a specific billing code
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LikelihoodData {
     /// Medical billing code
@@ -158,40 +1694,594 @@ pub struct LikelihoodData {
     pub likelihood: f64,
 }
 
-/// Metadata for pricing responses
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct PricingMeta {
-    /// Insurance plan identifier
-    pub plan_id: String,
-    /// Insurance payer code
-    pub payer: String,
-    /// Unique request identifier
-    pub request_id: String,
-    /// Request timestamp in ISO 8601 format
-    pub timestamp: DateTime<Utc>,
-    /// Processing time in milliseconds
-    pub processing_time_ms: u32,
-    /// Number of in-network records found
-    pub in_network_records_count: u32,
-}
+impl LikelihoodData {
+    /// Interpret the likelihood score as a confidence band
+    ///
+    /// Band boundaries are the constants in [`thresholds`](crate::procedures::thresholds):
+    /// each band includes its lower bound, so `0.8` is highly likely and `0.6` likely.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use docaroo_rs::models::{ConfidenceBand, LikelihoodData};
+    ///
+    /// let data = LikelihoodData {
+    ///     code: "99214".to_string(),
+    ///     code_type: "CPT".to_string(),
+    ///     likelihood: 0.65,
+    /// };
+    /// assert_eq!(data.confidence_band(), ConfidenceBand::Likely);
+    /// assert_eq!(data.confidence_band().to_string(), "Likely");
+    /// ```
+    pub fn confidence_band(&self) -> ConfidenceBand {
+        use crate::procedures::thresholds;
 
-/// Metadata for likelihood responses
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct LikelihoodMeta {
-    /// Unique request identifier
-    pub request_id: String,
-    /// Request timestamp in ISO 8601 format
-    pub timestamp: DateTime<Utc>,
-    /// Processing time in milliseconds
-    pub processing_time_ms: u32,
-    /// Number of out-of-network records analyzed
-    pub out_of_network_records_count: u32,
-}
+        match self.likelihood {
+            x if x >= thresholds::HIGHLY_LIKELY => ConfidenceBand::HighlyLikely,
+            x if x >= thresholds::LIKELY => ConfidenceBand::Likely,
+            x if x >= thresholds::MODERATE => ConfidenceBand::Moderate,
+            x if x >= thresholds::UNLIKELY => ConfidenceBand::Unlikely,
+            _ => ConfidenceBand::VeryUnlikely,
+        }
+    }
+
+    /// Check whether the likelihood score meets a threshold (inclusive)
+    ///
+    /// Typically called with one of the [`thresholds`](crate::procedures::thresholds)
+    /// constants.
+    pub fn above_threshold(&self, threshold: f64) -> bool {
+        self.likelihood >= threshold
+    }
+}
+
+impl LikelihoodResponse {
+    /// Likelihood score for an NPI, or `None` if the response has no entry for it
+    ///
+    /// Unlike indexing `data` directly, a missing NPI does not panic.
+    pub fn score_for(&self, npi: &str) -> Option<&LikelihoodData> {
+        self.data.get(npi)
+    }
+
+    /// NPIs present in the response
+    pub fn npis(&self) -> impl Iterator<Item = &str> {
+        self.data.keys().map(String::as_str)
+    }
+
+    /// Check whether any NPI's likelihood score meets a threshold (inclusive)
+    pub fn any_above(&self, threshold: f64) -> bool {
+        self.data.values().any(|data| data.above_threshold(threshold))
+    }
+
+    /// NPIs whose likelihood score meets a threshold (inclusive), in map order
+    ///
+    /// NaN scores never meet a threshold.
+    pub fn filter_above(&self, threshold: f64) -> impl Iterator<Item = (&str, &LikelihoodData)> {
+        self.data
+            .iter()
+            .filter(move |(_, data)| data.above_threshold(threshold))
+            .map(|(npi, data)| (npi.as_str(), data))
+    }
+
+    /// Every NPI's score, highest likelihood first
+    ///
+    /// The order is deterministic: NaN scores sort after every number, and equal
+    /// scores are ordered by NPI.
+    pub fn ranked(&self) -> Vec<(&str, &LikelihoodData)> {
+        let mut ranked: Vec<(&str, &LikelihoodData)> = self
+            .data
+            .iter()
+            .map(|(npi, data)| (npi.as_str(), data))
+            .collect();
+        ranked.sort_by(|(a_npi, a), (b_npi, b)| {
+            cmp_desc_nan_last(a.likelihood, b.likelihood).then_with(|| a_npi.cmp(b_npi))
+        });
+        ranked
+    }
+}
+
+/// Interpretation of a likelihood score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ConfidenceBand {
+    /// Below [`thresholds::UNLIKELY`](crate::procedures::thresholds::UNLIKELY)
+    VeryUnlikely,
+    /// At least [`thresholds::UNLIKELY`](crate::procedures::thresholds::UNLIKELY)
+    Unlikely,
+    /// At least [`thresholds::MODERATE`](crate::procedures::thresholds::MODERATE)
+    Moderate,
+    /// At least [`thresholds::LIKELY`](crate::procedures::thresholds::LIKELY)
+    Likely,
+    /// At least [`thresholds::HIGHLY_LIKELY`](crate::procedures::thresholds::HIGHLY_LIKELY)
+    HighlyLikely,
+}
+
+impl std::fmt::Display for ConfidenceBand {
+    /// Human-readable label, e.g. `"Moderately likely"`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::VeryUnlikely => "Very unlikely",
+            Self::Unlikely => "Unlikely",
+            Self::Moderate => "Moderately likely",
+            Self::Likely => "Likely",
+            Self::HighlyLikely => "Highly likely",
+        })
+    }
+}
+
+/// A National Provider Identifier that has passed validation
+///
+/// Constructing an `Npi` runs [`validate_npi`](crate::validation::validate_npi), so
+/// an invalid NPI is rejected where it enters the program rather than when a request
+/// is sent. Deserializing validates the same way.
+///
+/// # Example
+///
+/// ```
+/// use docaroo_rs::models::{Npi, PricingRequest};
+///
+/// let npi = Npi::new("1043566623")?;
+/// assert!(Npi::new("1043566624").is_err()); // bad check digit
+///
+/// let request = PricingRequest::builder()
+///     .typed_npis([npi])
+///     .condition_code("99214")
+///     .build();
+/// assert_eq!(request.npis, vec!["1043566623"]);
+/// # Ok::<(), docaroo_rs::DocarooError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct Npi(String);
+
+impl Npi {
+    /// Validate and wrap an NPI
+    ///
+    /// # Errors
+    ///
+    /// Returns `DocarooError::InvalidRequest` if the NPI is not 10 digits or fails its
+    /// check digit
+    pub fn new(npi: impl Into<String>) -> crate::Result<Self> {
+        let npi = npi.into();
+        crate::validation::validate_npi(&npi)?;
+        Ok(Self(npi))
+    }
+
+    /// The NPI as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Unwrap into the underlying string
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl std::ops::Deref for Npi {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Npi {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for Npi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::str::FromStr for Npi {
+    type Err = crate::DocarooError;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for Npi {
+    type Error = crate::DocarooError;
+
+    fn try_from(npi: String) -> crate::Result<Self> {
+        Self::new(npi)
+    }
+}
+
+impl TryFrom<&str> for Npi {
+    type Error = crate::DocarooError;
+
+    fn try_from(npi: &str) -> crate::Result<Self> {
+        Self::new(npi)
+    }
+}
+
+impl From<Npi> for String {
+    fn from(npi: Npi) -> Self {
+        npi.0
+    }
+}
+
+impl PartialEq<str> for Npi {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Npi {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+/// Insurance plan identifier, classified by its format
+///
+/// The API accepts an employer EIN, a HIOS plan ID or a custom plan ID in the same
+/// field. Parsing detects the first two by shape and falls back to
+/// [`Custom`](Self::Custom), so any string is accepted; checking the variant catches
+/// values that landed in the wrong field, such as an NPI passed as a plan ID.
+///
+/// # Example
+///
+/// ```
+/// use docaroo_rs::models::{PlanId, PricingRequest};
+///
+/// assert!(matches!(PlanId::from("942404110"), PlanId::Ein(_)));
+/// assert!(matches!(PlanId::from("12345NY0010001"), PlanId::Hios(_)));
+/// assert!(matches!(PlanId::from("acme-gold-2025"), PlanId::Custom(_)));
+///
+/// let request = PricingRequest::builder()
+///     .npis(vec!["1043566623".to_string()])
+///     .condition_code("99214")
+///     .plan_id(PlanId::from("942404110"))
+///     .build();
+/// assert_eq!(request.plan_id.as_deref(), Some("942404110"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "String", from = "String")]
+pub enum PlanId {
+    /// Employer Identification Number: 9 digits, optionally written `12-3456789`
+    Ein(String),
+    /// HIOS plan ID: 5-digit issuer ID, 2-letter state code and 7 digits, e.g.
+    /// `12345NY0010001`, optionally followed by a `-01` style variant suffix
+    Hios(String),
+    /// Any other identifier
+    Custom(String),
+}
+
+impl PlanId {
+    /// Classify a plan identifier by its format, keeping it as given
+    pub fn parse(id: impl Into<String>) -> Self {
+        let id = id.into();
+        if Self::is_ein(&id) {
+            Self::Ein(id)
+        } else if Self::is_hios(&id) {
+            Self::Hios(id)
+        } else {
+            Self::Custom(id)
+        }
+    }
+
+    /// The identifier as a string slice
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Ein(id) | Self::Hios(id) | Self::Custom(id) => id,
+        }
+    }
+
+    /// Unwrap into the underlying string
+    pub fn into_inner(self) -> String {
+        match self {
+            Self::Ein(id) | Self::Hios(id) | Self::Custom(id) => id,
+        }
+    }
+
+    fn is_ein(id: &str) -> bool {
+        match id.split_once('-') {
+            Some((prefix, number)) => all_digits(prefix, 2) && all_digits(number, 7),
+            None => all_digits(id, 9),
+        }
+    }
+
+    fn is_hios(id: &str) -> bool {
+        let (base, variant) = match id.split_once('-') {
+            Some((base, variant)) => (base, Some(variant)),
+            None => (id, None),
+        };
+        base.len() == 14
+            && base.is_ascii()
+            && all_digits(&base[..5], 5)
+            && base.as_bytes()[5..7].iter().all(u8::is_ascii_uppercase)
+            && all_digits(&base[7..], 7)
+            && variant.is_none_or(|variant| all_digits(variant, 2))
+    }
+}
+
+/// Whether `s` is exactly `len` ASCII digits
+fn all_digits(s: &str, len: usize) -> bool {
+    s.len() == len && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+impl std::ops::Deref for PlanId {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for PlanId {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl std::fmt::Display for PlanId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for PlanId {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(Self::parse(s))
+    }
+}
+
+impl From<String> for PlanId {
+    fn from(id: String) -> Self {
+        Self::parse(id)
+    }
+}
+
+impl From<&str> for PlanId {
+    fn from(id: &str) -> Self {
+        Self::parse(id)
+    }
+}
+
+impl From<PlanId> for String {
+    fn from(id: PlanId) -> Self {
+        id.into_inner()
+    }
+}
+
+/// Insurance payer code reported in pricing metadata (e.g. `UNH`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Payer(String);
+
+impl Payer {
+    /// Create a payer from its code
+    pub fn new(code: impl Into<String>) -> Self {
+        Self(code.into())
+    }
+
+    /// Get the payer code
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::ops::Deref for Payer {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for Payer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<String> for Payer {
+    fn from(code: String) -> Self {
+        Self(code)
+    }
+}
+
+impl From<&str> for Payer {
+    fn from(code: &str) -> Self {
+        Self(code.to_string())
+    }
+}
+
+impl PartialEq<str> for Payer {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Payer {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+/// Metadata for pricing responses
+///
+/// Serializes back to the wire format, with `timestamp` exactly as the server sent it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", from = "PricingMetaWire", into = "PricingMetaWire")]
+pub struct PricingMeta {
+    /// Insurance plan identifier
+    pub plan_id: String,
+    /// Insurance payer code
+    pub payer: Payer,
+    /// Unique request identifier
+    pub request_id: String,
+    /// Request timestamp, or `None` if the server sent one that could not be parsed
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Request timestamp exactly as the server sent it
+    pub timestamp_raw: Option<String>,
+    /// Processing time in milliseconds
+    pub processing_time_ms: u32,
+    /// Number of in-network records found
+    pub in_network_records_count: u32,
+}
+
+/// Wire format of [`PricingMeta`], with the timestamp left unparsed
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PricingMetaWire {
+    plan_id: String,
+    payer: Payer,
+    request_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timestamp: Option<String>,
+    processing_time_ms: u32,
+    in_network_records_count: u32,
+}
+
+impl From<PricingMeta> for PricingMetaWire {
+    fn from(meta: PricingMeta) -> Self {
+        Self {
+            plan_id: meta.plan_id,
+            payer: meta.payer,
+            request_id: meta.request_id,
+            timestamp: raw_timestamp(meta.timestamp_raw, meta.timestamp),
+            processing_time_ms: meta.processing_time_ms,
+            in_network_records_count: meta.in_network_records_count,
+        }
+    }
+}
+
+impl From<PricingMetaWire> for PricingMeta {
+    fn from(wire: PricingMetaWire) -> Self {
+        Self {
+            plan_id: wire.plan_id,
+            payer: wire.payer,
+            request_id: wire.request_id,
+            timestamp: parse_timestamp(wire.timestamp.as_deref()),
+            timestamp_raw: wire.timestamp,
+            processing_time_ms: wire.processing_time_ms,
+            in_network_records_count: wire.in_network_records_count,
+        }
+    }
+}
+
+impl PricingMeta {
+    /// How long ago the server produced this response
+    ///
+    /// Clamped to zero if the server's clock is ahead of ours.
+    ///
+    /// # Returns
+    ///
+    /// The age, or `None` if the response timestamp is missing or could not be parsed
+    /// (see [`timestamp_raw`](Self::timestamp_raw))
+    pub fn age(&self) -> Option<chrono::Duration> {
+        self.age_at(Utc::now())
+    }
+
+    /// How old this response is relative to `now`, clamped to zero
+    ///
+    /// # Returns
+    ///
+    /// The age, or `None` if the response timestamp is missing or could not be parsed
+    pub fn age_at(&self, now: DateTime<Utc>) -> Option<chrono::Duration> {
+        self.timestamp
+            .map(|timestamp| (now - timestamp).max(chrono::Duration::zero()))
+    }
+}
+
+/// Metadata for likelihood responses
+///
+/// Serializes back to the wire format, with `timestamp` exactly as the server sent it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", from = "LikelihoodMetaWire", into = "LikelihoodMetaWire")]
+pub struct LikelihoodMeta {
+    /// Unique request identifier
+    pub request_id: String,
+    /// Request timestamp, or `None` if the server sent one that could not be parsed
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Request timestamp exactly as the server sent it
+    pub timestamp_raw: Option<String>,
+    /// Processing time in milliseconds
+    pub processing_time_ms: u32,
+    /// Number of out-of-network records analyzed
+    pub out_of_network_records_count: u32,
+}
+
+/// Wire format of [`LikelihoodMeta`], with the timestamp left unparsed
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LikelihoodMetaWire {
+    request_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timestamp: Option<String>,
+    processing_time_ms: u32,
+    out_of_network_records_count: u32,
+}
+
+impl From<LikelihoodMeta> for LikelihoodMetaWire {
+    fn from(meta: LikelihoodMeta) -> Self {
+        Self {
+            request_id: meta.request_id,
+            timestamp: raw_timestamp(meta.timestamp_raw, meta.timestamp),
+            processing_time_ms: meta.processing_time_ms,
+            out_of_network_records_count: meta.out_of_network_records_count,
+        }
+    }
+}
+
+impl From<LikelihoodMetaWire> for LikelihoodMeta {
+    fn from(wire: LikelihoodMetaWire) -> Self {
+        Self {
+            request_id: wire.request_id,
+            timestamp: parse_timestamp(wire.timestamp.as_deref()),
+            timestamp_raw: wire.timestamp,
+            processing_time_ms: wire.processing_time_ms,
+            out_of_network_records_count: wire.out_of_network_records_count,
+        }
+    }
+}
+
+impl LikelihoodMeta {
+    /// How long ago the server produced this response
+    ///
+    /// Clamped to zero if the server's clock is ahead of ours.
+    ///
+    /// # Returns
+    ///
+    /// The age, or `None` if the response timestamp is missing or could not be parsed
+    /// (see [`timestamp_raw`](Self::timestamp_raw))
+    pub fn age(&self) -> Option<chrono::Duration> {
+        self.age_at(Utc::now())
+    }
+
+    /// How old this response is relative to `now`, clamped to zero
+    ///
+    /// # Returns
+    ///
+    /// The age, or `None` if the response timestamp is missing or could not be parsed
+    pub fn age_at(&self, now: DateTime<Utc>) -> Option<chrono::Duration> {
+        self.timestamp
+            .map(|timestamp| (now - timestamp).max(chrono::Duration::zero()))
+    }
+}
+
+/// Parse an ISO 8601 timestamp, returning `None` rather than failing on bad input
+fn parse_timestamp(raw: Option<&str>) -> Option<DateTime<Utc>> {
+    raw.and_then(|raw| raw.parse().ok())
+}
+
+/// Timestamp to serialize: the server's original string, or the parsed value when the
+/// meta was built by hand
+fn raw_timestamp(raw: Option<String>, parsed: Option<DateTime<Utc>>) -> Option<String> {
+    raw.or_else(|| parsed.map(|timestamp| timestamp.to_rfc3339()))
+}
 
 /// Error response from the API
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ErrorResponse {
     /// Error type
@@ -214,6 +2304,23 @@ pub struct ErrorResponse {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_code_type_from_alias() {
+        assert_eq!(CodeType::from_alias("drg").unwrap(), CodeType::MsDrg);
+        assert_eq!(CodeType::from_alias("proc").unwrap(), CodeType::Cpt);
+        assert_eq!(CodeType::from_alias("Revenue").unwrap(), CodeType::Rc);
+        assert_eq!(CodeType::from_alias("ms_drg").unwrap(), CodeType::MsDrg);
+        assert_eq!(CodeType::from_alias(" hipps ").unwrap(), CodeType::Hipps);
+
+        for &code_type in CodeType::all() {
+            assert_eq!(CodeType::from_alias(code_type.as_str()).unwrap(), code_type);
+        }
+
+        let error = CodeType::from_alias("procs").unwrap_err();
+        assert!(matches!(error, crate::DocarooError::InvalidRequest(_)));
+        assert!(error.to_string().contains("procs"));
+    }
+
     #[test]
     fn test_pricing_request_builder() {
         let request = PricingRequest::builder()
@@ -234,12 +2341,501 @@ mod tests {
         let request = LikelihoodRequest::builder()
             .npis(vec!["1487648176".to_string()])
             .condition_code("99214")
-            .code_type("CPT")
+            .code_type(CodeType::Cpt)
             .build();
 
         assert_eq!(request.npis.len(), 1);
         assert_eq!(request.condition_code, "99214");
-        assert_eq!(request.code_type, "CPT");
+        assert_eq!(request.code_type, CodeType::Cpt);
+    }
+
+    #[test]
+    fn test_pricing_request_with_methods() {
+        let base = PricingRequest::builder()
+            .npis(vec!["1043566623".to_string()])
+            .condition_code("99214")
+            .build();
+
+        let variant = base.with_condition_code("99215");
+        assert_eq!(base.condition_code, "99214");
+        assert_eq!(variant.condition_code, "99215");
+        assert_eq!(variant.npis, base.npis);
+
+        let variant = base
+            .with_npis(vec!["1972767655".to_string()])
+            .with_plan_id("942404110")
+            .with_code_type(CodeType::Hcpcs);
+        assert_eq!(base.npis, vec!["1043566623".to_string()]);
+        assert_eq!(base.plan_id, None);
+        assert_eq!(base.code_type, None);
+        assert_eq!(variant.npis, vec!["1972767655".to_string()]);
+        assert_eq!(variant.plan_id, Some("942404110".to_string()));
+        assert_eq!(variant.code_type, Some(CodeType::Hcpcs));
+    }
+
+    #[test]
+    fn test_likelihood_request_with_methods() {
+        let base = LikelihoodRequest::builder()
+            .npis(vec!["1487648176".to_string()])
+            .condition_code("99214")
+            .code_type(CodeType::Cpt)
+            .build();
+
+        let variant = base
+            .with_npis(vec!["1043566623".to_string()])
+            .with_condition_code("G0008")
+            .with_code_type(CodeType::Hcpcs);
+        assert_eq!(base.npis, vec!["1487648176".to_string()]);
+        assert_eq!(base.condition_code, "99214");
+        assert_eq!(base.code_type, CodeType::Cpt);
+        assert_eq!(variant.npis, vec!["1043566623".to_string()]);
+        assert_eq!(variant.condition_code, "G0008");
+        assert_eq!(variant.code_type, CodeType::Hcpcs);
+    }
+
+    #[test]
+    fn test_try_code_type() {
+        let request = LikelihoodRequest::builder()
+            .npis(vec!["1487648176".to_string()])
+            .condition_code("99214")
+            .try_code_type("ms-drg")
+            .unwrap()
+            .build();
+        assert_eq!(request.code_type, CodeType::MsDrg);
+        assert_eq!(request.code_type, "MS-DRG");
+
+        let result = LikelihoodRequest::builder().try_code_type("CTP");
+        assert!(matches!(result, Err(crate::DocarooError::InvalidRequest(_))));
+
+        assert_eq!("hcpcs".try_into_code_type().unwrap(), CodeType::Hcpcs);
+        assert!(String::from("CTP").try_into_code_type().is_err());
+    }
+
+    #[test]
+    fn test_npi_newtype() {
+        let npi = Npi::new("1043566623").unwrap();
+        assert_eq!(npi, "1043566623");
+        assert_eq!(npi.len(), 10);
+        assert_eq!(npi.to_string(), "1043566623");
+        assert_eq!("1972767655".parse::<Npi>().unwrap().as_str(), "1972767655");
+
+        for invalid in ["104356662", "10435666AB", "1043566624"] {
+            let error = Npi::new(invalid).unwrap_err();
+            assert!(matches!(error, crate::DocarooError::InvalidRequest(_)), "{invalid}");
+        }
+
+        // Serde is transparent and validates on the way in
+        assert_eq!(serde_json::to_string(&npi).unwrap(), r#""1043566623""#);
+        assert!(serde_json::from_str::<Npi>(r#""1043566623""#).is_ok());
+        assert!(serde_json::from_str::<Npi>(r#""1043566624""#).is_err());
+
+        let request = LikelihoodRequest::builder()
+            .typed_npis(vec![npi, Npi::new("1487648176").unwrap()])
+            .condition_code("99214")
+            .code_type(CodeType::Cpt)
+            .build();
+        assert_eq!(request.npis, vec!["1043566623", "1487648176"]);
+    }
+
+    #[test]
+    fn test_plan_id_formats() {
+        for ein in ["942404110", "94-2404110"] {
+            assert_eq!(PlanId::from(ein), PlanId::Ein(ein.to_string()));
+        }
+        for hios in ["12345NY0010001", "12345NY0010001-01"] {
+            assert_eq!(PlanId::from(hios), PlanId::Hios(hios.to_string()));
+        }
+        for custom in [
+            "custom-plan",
+            "1043566623",       // an NPI is one digit too long for an EIN
+            "9424041",          // too short for an EIN
+            "942-404110",       // EIN dash in the wrong place
+            "12345ny0010001",   // HIOS state code must be upper case
+            "12345NY001000",    // HIOS plan number too short
+            "12345NY0010001-1", // HIOS variant must be 2 digits
+            "12345ÑY001000",    // non-ASCII must not panic
+            "",
+        ] {
+            assert_eq!(PlanId::from(custom), PlanId::Custom(custom.to_string()));
+        }
+
+        let plan_id: PlanId = "942404110".parse().unwrap();
+        assert_eq!(plan_id.to_string(), "942404110");
+        assert_eq!(serde_json::to_string(&plan_id).unwrap(), r#""942404110""#);
+        let parsed: PlanId = serde_json::from_str(r#""12345NY0010001""#).unwrap();
+        assert!(matches!(parsed, PlanId::Hios(_)));
+
+        // The builder takes either form
+        let request = PricingRequest::builder()
+            .npis(vec!["1043566623".to_string()])
+            .condition_code("99214")
+            .plan_id(PlanId::Hios("12345NY0010001".to_string()))
+            .build();
+        assert_eq!(request.plan_id.as_deref(), Some("12345NY0010001"));
+        assert_eq!(
+            request.with_plan_id("942404110").typed_plan_id(),
+            Some(PlanId::Ein("942404110".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_npis_serialize_in_sorted_order() {
+        let forward = PricingRequest::builder()
+            .npis(vec![
+                "1043566623".to_string(),
+                "1487648176".to_string(),
+                "1972767655".to_string(),
+            ])
+            .condition_code("99214")
+            .build();
+        let shuffled = forward.with_npis(vec![
+            "1972767655".to_string(),
+            "1043566623".to_string(),
+            "1487648176".to_string(),
+        ]);
+
+        let json = serde_json::to_string(&shuffled).unwrap();
+        assert_eq!(json, serde_json::to_string(&forward).unwrap());
+        assert!(json.contains(r#""npis":["1043566623","1487648176","1972767655"]"#));
+        // The request itself keeps the caller's order
+        assert_eq!(shuffled.npis[0], "1972767655");
+
+        let likelihood = |npis: &[&str]| {
+            let request = LikelihoodRequest::builder()
+                .npis(npis.iter().map(|npi| npi.to_string()).collect::<Vec<_>>())
+                .condition_code("99214")
+                .code_type(CodeType::Cpt)
+                .build();
+            serde_json::to_string(&request).unwrap()
+        };
+        assert_eq!(
+            likelihood(&["1487648176", "1043566623"]),
+            likelihood(&["1043566623", "1487648176"])
+        );
+    }
+
+    #[test]
+    fn test_request_json_round_trip() {
+        let mut request = PricingRequest::builder()
+            .npis(vec!["1043566623".to_string(), "1972767655".to_string()])
+            .condition_code("99214")
+            .build();
+        request.tag_npi("1043566623", "dr-smith");
+        let json = serde_json::to_string(&request).unwrap();
+        assert!(!json.contains("planId") && !json.contains("codeType"));
+
+        let restored: PricingRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.npis, request.npis);
+        assert_eq!(restored.condition_code, "99214");
+        assert_eq!(restored.plan_id, None);
+        assert_eq!(restored.code_type, None);
+        assert!(restored.tags.is_empty());
+
+        let request = request
+            .with_plan_id("942404110")
+            .with_code_type(CodeType::Hcpcs);
+        let restored: PricingRequest =
+            serde_json::from_str(&serde_json::to_string(&request).unwrap()).unwrap();
+        assert_eq!(restored.plan_id.as_deref(), Some("942404110"));
+        assert_eq!(restored.code_type, Some(CodeType::Hcpcs));
+
+        let request = LikelihoodRequest::builder()
+            .npis(vec!["1043566623".to_string(), "1487648176".to_string()])
+            .condition_code("0470")
+            .code_type(CodeType::MsDrg)
+            .build();
+        let restored: LikelihoodRequest =
+            serde_json::from_str(&serde_json::to_string(&request).unwrap()).unwrap();
+        assert_eq!(restored.npis, request.npis);
+        assert_eq!(restored.condition_code, "0470");
+        assert_eq!(restored.code_type, CodeType::MsDrg);
+    }
+
+    #[test]
+    fn test_likelihood_request_code_type_wire_format() {
+        for (code_type, wire) in [
+            (CodeType::Cpt, "CPT"),
+            (CodeType::Hcpcs, "HCPCS"),
+            (CodeType::MsDrg, "MS-DRG"),
+            (CodeType::CstmAll, "CSTM-ALL"),
+        ] {
+            let request = LikelihoodRequest::builder()
+                .npis(vec!["1487648176".to_string()])
+                .condition_code("99214")
+                .code_type(code_type)
+                .build();
+            let json = serde_json::to_value(&request).unwrap();
+            assert_eq!(json["codeType"], wire);
+        }
+    }
+
+    #[test]
+    fn test_code_type_from_str() {
+        assert_eq!("CPT".parse::<CodeType>().unwrap(), CodeType::Cpt);
+        assert_eq!("APR-DRG".parse::<CodeType>().unwrap(), CodeType::AprDrg);
+        assert_eq!(CodeType::try_from("NDC").unwrap(), CodeType::Ndc);
+        assert_eq!("ms-drg".parse::<CodeType>().unwrap(), CodeType::MsDrg);
+        assert_eq!("Cstm-All".parse::<CodeType>().unwrap(), CodeType::CstmAll);
+
+        // Wire names only; aliases go through from_alias
+        assert!("drg".parse::<CodeType>().is_err());
+        let error = CodeType::try_from("CTP").unwrap_err();
+        assert!(matches!(error, crate::DocarooError::InvalidRequest(_)));
+        assert!(error.to_string().contains("CTP"));
+    }
+
+    #[test]
+    fn test_code_type_display_round_trips() {
+        for &code_type in CodeType::all() {
+            assert_eq!(code_type.to_string(), code_type.as_str());
+            assert_eq!(code_type.to_string().parse::<CodeType>().unwrap(), code_type);
+        }
+    }
+
+    #[test]
+    fn test_code_type_as_str_matches_serde() {
+        for code_type in [CodeType::Cpt, CodeType::Hcpcs, CodeType::AprDrg, CodeType::CstmAll] {
+            let json = serde_json::to_string(&code_type).unwrap();
+            assert_eq!(json, format!("\"{}\"", code_type.as_str()));
+        }
+    }
+
+    #[test]
+    fn test_npis_from_ints() {
+        let request = PricingRequest::builder()
+            .npis_from_ints([1043566623, 43566625])
+            .unwrap()
+            .condition_code("99214")
+            .build();
+        assert_eq!(request.npis, vec!["1043566623".to_string(), "0043566625".to_string()]);
+
+        let request = LikelihoodRequest::builder()
+            .npis_from_ints([1487648176])
+            .unwrap()
+            .condition_code("99214")
+            .code_type(CodeType::Cpt)
+            .build();
+        assert_eq!(request.npis, vec!["1487648176".to_string()]);
+    }
+
+    #[test]
+    fn test_npis_from_ints_too_large() {
+        let result = PricingRequest::builder().npis_from_ints([1043566623, 10_435_666_230]);
+        let message = result.err().unwrap().to_string();
+        assert!(message.contains("10435666230"));
+        assert!(message.contains("more than 10 digits"));
+    }
+
+    #[test]
+    fn test_npis_from_ints_checks_check_digit() {
+        let result = PricingRequest::builder().npis_from_ints([1043566623, 1043566624]);
+        let message = result.err().unwrap().to_string();
+        assert!(message.contains("1043566624"));
+        assert!(message.contains("check digit"));
+
+        let result = LikelihoodRequest::builder().npis_from_ints([43566623]);
+        assert!(result.err().unwrap().to_string().contains("0043566623"));
+    }
+
+    #[test]
+    fn test_code_description() {
+        assert_eq!(
+            code_description("99214", CodeType::Cpt),
+            Some("Office visit, moderate complexity")
+        );
+        assert_eq!(
+            code_description("90834", CodeType::Cpt),
+            Some("Psychotherapy, 45 minutes")
+        );
+        assert_eq!(
+            code_description("J0180", CodeType::Hcpcs),
+            Some("Injection, agalsidase beta, 1 mg")
+        );
+        assert_eq!(code_description("0260", CodeType::Rc), Some("IV therapy, general"));
+
+        assert_eq!(code_description("00000", CodeType::Cpt), None);
+        assert_eq!(code_description("99214", CodeType::Hcpcs), None);
+    }
+
+    #[test]
+    fn test_npi_entity_type() {
+        assert_eq!(npi_entity_type("1043566623"), None);
+        assert_eq!(npi_entity_type("1972767655"), None);
+        assert_eq!(npi_entity_type("123"), None);
+    }
+
+    #[test]
+    fn test_pricing_request_lint() {
+        let request = PricingRequest::builder()
+            .npis(vec![
+                "1043566623".to_string(),
+                "1972767655".to_string(),
+                "1487648176".to_string(),
+            ])
+            .condition_code("99214")
+            .build();
+        assert!(request.lint().is_empty());
+
+        let individuals = |npi: &str| (npi != "1487648176").then_some(NpiEntityType::Individual);
+        assert!(request.lint_with(individuals).is_empty());
+
+        let warnings = request.lint_with(|npi| match npi {
+            "1043566623" => Some(NpiEntityType::Individual),
+            "1972767655" => Some(NpiEntityType::Organization),
+            _ => None,
+        });
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("individual and organizational"));
+    }
+
+    #[test]
+    fn test_full_report_collects_every_problem() {
+        let mut npis = vec!["1043566623".to_string(); 9];
+        npis.insert(2, "12345".to_string());
+        npis.push("1234567890".to_string());
+        let request = PricingRequest::builder()
+            .npis(npis)
+            .condition_code("9921")
+            .code_type(CodeType::Cpt)
+            .build();
+
+        let report = request.full_report();
+        assert!(!report.is_valid());
+        assert_eq!(report.count_error.as_deref(), Some("Maximum 10 NPIs allowed per request"));
+        assert_eq!(report.npi_errors.len(), 2);
+        assert_eq!(report.npi_errors[0].0, 2);
+        assert!(report.npi_errors[0].1.contains("wrong length"));
+        assert_eq!(report.npi_errors[1].0, 10);
+        assert!(report.npi_errors[1].1.contains("checksum"));
+        assert!(report.code_error.is_none());
+        assert!(report.code_type_error.unwrap().contains("Invalid CPT code '9921'"));
+
+        let request = LikelihoodRequest::builder()
+            .npis(Vec::<String>::new())
+            .condition_code(" ")
+            .code_type(CodeType::Cpt)
+            .build();
+        let report = request.full_report();
+        assert!(report.count_error.is_some());
+        assert!(report.code_error.is_some());
+        assert!(report.code_type_error.is_none());
+
+        let request = request.with_npis(vec!["1043566623".to_string()]).with_condition_code("99214");
+        assert_eq!(request.full_report(), ValidationReport::default());
+        assert!(request.full_report().is_valid());
+    }
+
+    #[test]
+    fn test_pricing_try_build() {
+        use crate::error::DocarooError;
+
+        let request = PricingRequest::builder()
+            .npis(vec!["1043566623".to_string()])
+            .condition_code("99214")
+            .try_build()
+            .unwrap();
+        assert_eq!(request.npis, vec!["1043566623"]);
+
+        let cases: [(Vec<&str>, &str, &str); 5] = [
+            (vec![], "99214", "At least one NPI"),
+            (vec!["1043566623"; 11], "99214", "Maximum 10 NPIs"),
+            (vec!["12345"], "99214", "wrong length"),
+            (vec!["1234567890"], "99214", "checksum"),
+            (vec!["1043566623"], "  ", "Condition code cannot be empty"),
+        ];
+        for (npis, code, expected) in cases {
+            let err = PricingRequest::builder()
+                .npis(npis.iter().map(|npi| npi.to_string()).collect::<Vec<_>>())
+                .condition_code(code)
+                .try_build()
+                .unwrap_err();
+            assert!(
+                matches!(&err, DocarooError::InvalidRequest(m) if m.contains(expected)),
+                "expected {expected:?}, got {err:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_likelihood_try_build() {
+        use crate::error::DocarooError;
+
+        let request = LikelihoodRequest::builder()
+            .npis(vec!["1043566623".to_string()])
+            .condition_code("99214")
+            .code_type(CodeType::Cpt)
+            .try_build()
+            .unwrap();
+        assert_eq!(request.code_type, CodeType::Cpt);
+
+        let cases: [(Vec<&str>, &str, &str); 5] = [
+            (vec![], "99214", "At least one NPI"),
+            (vec!["1043566623"; 11], "99214", "Maximum 10 NPIs"),
+            (vec!["12345"], "99214", "wrong length"),
+            (vec!["1234567890"], "99214", "checksum"),
+            (vec!["1043566623"], "", "Condition code cannot be empty"),
+        ];
+        for (npis, code, expected) in cases {
+            let err = LikelihoodRequest::builder()
+                .npis(npis.iter().map(|npi| npi.to_string()).collect::<Vec<_>>())
+                .condition_code(code)
+                .code_type(CodeType::Cpt)
+                .try_build()
+                .unwrap_err();
+            assert!(
+                matches!(&err, DocarooError::InvalidRequest(m) if m.contains(expected)),
+                "expected {expected:?}, got {err:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_all_collects_every_error() {
+        use crate::error::DocarooError;
+
+        let request = PricingRequest::builder()
+            .npis(vec![
+                "1043566623".to_string(),
+                "12345".to_string(),
+                "1234567890".to_string(),
+                "1972767655".to_string(),
+            ])
+            .condition_code("")
+            .build();
+        assert!(request.validate().is_err());
+
+        let errors = request.validate_all().unwrap_err();
+        let messages: Vec<String> = errors
+            .iter()
+            .map(|error| match error {
+                DocarooError::InvalidRequest(message) => message.clone(),
+                other => panic!("Unexpected error: {other:?}"),
+            })
+            .collect();
+        assert_eq!(messages.len(), 3);
+        assert!(messages[0].contains("12345") && messages[0].contains("wrong length"));
+        assert!(messages[1].contains("1234567890") && messages[1].contains("checksum"));
+        assert_eq!(messages[2], "Condition code cannot be empty");
+
+        let mut npis = vec!["1043566623".to_string(); 11];
+        npis[4] = "1A43566623".to_string();
+        let errors = request
+            .with_npis(npis)
+            .with_condition_code("99214")
+            .validate_all()
+            .unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].to_string(), "Invalid request: Maximum 10 NPIs allowed per request");
+
+        // Code type mismatches are only enforced under strict code validation
+        let request = LikelihoodRequest::builder()
+            .npis(vec!["1043566623".to_string()])
+            .condition_code("9921")
+            .code_type(CodeType::Cpt)
+            .build();
+        assert!(request.validate_all().is_ok());
+        let errors = request.with_npis(Vec::<String>::new()).validate_all().unwrap_err();
+        assert_eq!(errors.len(), 1);
     }
 
     #[test]
@@ -251,4 +2847,808 @@ mod tests {
         let deserialized: CodeType = serde_json::from_str(&json).unwrap();
         assert_eq!(deserialized, CodeType::Cpt);
     }
+
+    fn likelihood_data(likelihood: f64) -> LikelihoodData {
+        LikelihoodData {
+            code: "99214".to_string(),
+            code_type: "CPT".to_string(),
+            likelihood,
+        }
+    }
+
+    #[test]
+    fn test_band_boundaries() {
+        use crate::procedures::thresholds;
+
+        let cases = [
+            (thresholds::HIGHLY_LIKELY, ConfidenceBand::HighlyLikely),
+            (thresholds::LIKELY, ConfidenceBand::Likely),
+            (thresholds::MODERATE, ConfidenceBand::Moderate),
+            (thresholds::UNLIKELY, ConfidenceBand::Unlikely),
+        ];
+        for (threshold, band) in cases {
+            assert_eq!(likelihood_data(threshold).confidence_band(), band);
+            assert!(likelihood_data(threshold - 1e-9).confidence_band() < band);
+        }
+
+        assert_eq!(likelihood_data(1.0).confidence_band(), ConfidenceBand::HighlyLikely);
+        assert_eq!(likelihood_data(0.0).confidence_band(), ConfidenceBand::VeryUnlikely);
+    }
+
+    #[test]
+    fn test_confidence_band_documented_boundaries() {
+        let cases = [
+            (0.19, ConfidenceBand::VeryUnlikely, "Very unlikely"),
+            (0.2, ConfidenceBand::Unlikely, "Unlikely"),
+            (0.4, ConfidenceBand::Moderate, "Moderately likely"),
+            (0.6, ConfidenceBand::Likely, "Likely"),
+            (0.8, ConfidenceBand::HighlyLikely, "Highly likely"),
+        ];
+        for (likelihood, band, label) in cases {
+            let data = likelihood_data(likelihood);
+            assert_eq!(data.confidence_band(), band, "{likelihood}");
+            assert_eq!(data.confidence_band().to_string(), label);
+        }
+    }
+
+    #[test]
+    fn test_nan_last_comparators() {
+        let mut values = [1.5, -f64::NAN, 3.0, f64::NAN, -2.0];
+        values.sort_by(|a, b| cmp_asc_nan_last(*a, *b));
+        assert_eq!(values[..3], [-2.0, 1.5, 3.0]);
+        assert!(values[3..].iter().all(|value| value.is_nan()));
+
+        values.sort_by(|a, b| cmp_desc_nan_last(*a, *b));
+        assert_eq!(values[..3], [3.0, 1.5, -2.0]);
+        assert!(values[3..].iter().all(|value| value.is_nan()));
+    }
+
+    #[test]
+    fn test_best_rate_per_npi_ignores_nan_ties() {
+        let mut response = sample_pricing_response();
+        let rates = response.data.get_mut("1043566623").unwrap();
+        for avg_rate in [-f64::NAN, f64::NAN] {
+            let mut tied = rates[0].clone();
+            tied.avg_rate = avg_rate;
+            rates.push(tied);
+        }
+
+        let best = response.best_rate_per_npi();
+        assert_eq!(best["1043566623"].instances, 6);
+        assert_eq!(best["1043566623"].avg_rate, 147.03);
+    }
+
+    #[test]
+    fn test_ranked_handles_nan() {
+        let response = LikelihoodResponse {
+            data: NpiMap::from_iter([
+                ("1487648176".to_string(), likelihood_data(0.3)),
+                ("1234567893".to_string(), likelihood_data(f64::NAN)),
+                ("1043566623".to_string(), likelihood_data(0.85)),
+                ("1972767655".to_string(), likelihood_data(0.3)),
+                ("1111111112".to_string(), likelihood_data(-f64::NAN)),
+            ]),
+            meta: LikelihoodMeta {
+                request_id: "req_test456".to_string(),
+                timestamp: None,
+                timestamp_raw: None,
+                processing_time_ms: 731,
+                out_of_network_records_count: 68,
+            },
+            extra: HashMap::new(),
+        };
+
+        let npis: Vec<&str> = response.ranked().into_iter().map(|(npi, _)| npi).collect();
+        assert_eq!(
+            npis,
+            ["1043566623", "1487648176", "1972767655", "1111111112", "1234567893"]
+        );
+
+        let mut above: Vec<&str> = response.filter_above(0.3).map(|(npi, _)| npi).collect();
+        above.sort_unstable();
+        assert_eq!(above, ["1043566623", "1487648176", "1972767655"]);
+        assert_eq!(response.filter_above(f64::NAN).count(), 0);
+    }
+
+    #[test]
+    fn test_above_threshold() {
+        use crate::procedures::thresholds;
+
+        assert!(likelihood_data(thresholds::LIKELY).above_threshold(thresholds::LIKELY));
+        assert!(!likelihood_data(0.59).above_threshold(thresholds::LIKELY));
+
+        let response = LikelihoodResponse {
+            data: NpiMap::from_iter([
+                ("1487648176".to_string(), likelihood_data(0.3)),
+                ("1043566623".to_string(), likelihood_data(0.85)),
+            ]),
+            meta: LikelihoodMeta {
+                request_id: "req_test456".to_string(),
+                timestamp: Some(Utc::now()),
+                timestamp_raw: None,
+                processing_time_ms: 731,
+                out_of_network_records_count: 68,
+            },
+            extra: HashMap::new(),
+        };
+        assert!(response.any_above(thresholds::HIGHLY_LIKELY));
+        assert!(!response.any_above(0.9));
+
+        assert_eq!(response.score_for("1043566623").unwrap().likelihood, 0.85);
+        let above: Vec<&str> = response
+            .filter_above(thresholds::LIKELY)
+            .map(|(npi, _)| npi)
+            .collect();
+        assert_eq!(above, ["1043566623"]);
+        assert!(response.score_for("1972767655").is_none());
+        let mut npis: Vec<&str> = response.npis().collect();
+        npis.sort_unstable();
+        assert_eq!(npis, ["1043566623", "1487648176"]);
+    }
+
+    fn sample_pricing_response() -> PricingResponse {
+        serde_json::from_str(
+            r#"{
+                "data": {
+                    "1043566623": [
+                        {
+                            "code": "99214",
+                            "codeType": "CPT",
+                            "negotiatedType": "negotiated",
+                            "minRate": 65.87,
+                            "maxRate": 266.88,
+                            "avgRate": 147.03,
+                            "instances": 6
+                        },
+                        {
+                            "code": "99214",
+                            "codeType": "CPT",
+                            "negotiatedType": "fee schedule",
+                            "minRate": 80.0,
+                            "maxRate": 120.0,
+                            "avgRate": 100.0,
+                            "instances": 2
+                        }
+                    ],
+                    "1972767655": [
+                        {
+                            "code": "99214",
+                            "codeType": "CPT",
+                            "negotiatedType": "negotiated",
+                            "minRate": 90.5,
+                            "maxRate": 210.0,
+                            "avgRate": 150.25,
+                            "instances": 4
+                        }
+                    ]
+                },
+                "meta": {
+                    "planId": "942404110",
+                    "payer": "UNH",
+                    "requestId": "req_test123",
+                    "timestamp": "2025-06-15T23:15:48.734729Z",
+                    "processingTimeMs": 912,
+                    "inNetworkRecordsCount": 14
+                }
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_weighted_percentiles() {
+        let response = sample_pricing_response();
+
+        // Expanded data: 100.0 x2, 147.03 x6, 150.25 x4
+        let percentiles = response.weighted_percentiles(&[10.0, 25.0, 50.0, 75.0, 100.0]);
+        assert_eq!(percentiles, vec![100.0, 147.03, 147.03, 150.25, 150.25]);
+
+        let mut empty = response.clone();
+        empty.data.clear();
+        assert!(empty.weighted_percentiles(&[50.0]).is_empty());
+    }
+
+    #[test]
+    fn test_rates_for() {
+        let response = sample_pricing_response();
+        let rates = response.rates_for("1043566623").unwrap();
+        assert_eq!(rates.len(), 2);
+        assert_eq!(rates[0].avg_rate, 147.03);
+        assert!(response.rates_for("1487648176").is_none());
+
+        let mut npis: Vec<&str> = response.npis().collect();
+        npis.sort_unstable();
+        assert_eq!(npis, ["1043566623", "1972767655"]);
+    }
+
+    #[test]
+    fn test_best_rate_per_npi() {
+        let mut response = sample_pricing_response();
+        let rates = response.data.get_mut("1043566623").unwrap();
+        let mut tied = rates[0].clone();
+        tied.avg_rate = 120.0;
+        tied.negotiated_type = "percentage".to_string();
+        rates.push(tied);
+
+        let best = response.best_rate_per_npi();
+        assert_eq!(best.len(), 2);
+
+        // 6 instances beats 2; the 6-instance tie goes to the lower average
+        let rate = best["1043566623"];
+        assert_eq!(rate.instances, 6);
+        assert_eq!(rate.avg_rate, 120.0);
+        assert_eq!(rate.negotiated_type, "percentage");
+
+        assert_eq!(best["1972767655"].avg_rate, 150.25);
+    }
+
+    #[test]
+    fn test_rate_modifier() {
+        let json = r#"{
+            "code": "99214",
+            "codeType": "CPT",
+            "negotiatedType": "negotiated",
+            "minRate": 65.87,
+            "maxRate": 266.88,
+            "avgRate": 147.03,
+            "instances": 6,
+            "modifier": "25"
+        }"#;
+        let rate: RateData = serde_json::from_str(json).unwrap();
+        assert_eq!(rate.modifier.as_deref(), Some("25"));
+        assert_eq!(rate.full_code(), "99214-25");
+
+        let rate = &sample_pricing_response().data["1043566623"][0];
+        assert_eq!(rate.modifier, None);
+        assert_eq!(rate.full_code(), "99214");
+    }
+
+    #[test]
+    fn test_read_npis_from_reader() {
+        let input = "# providers for the June run\n\
+                     1043566623\n\
+                     \n\
+                     \t1972767655  \n\
+                     1487648176 # pediatrics\n\
+                     #1234567893\n";
+
+        let npis = read_npis_from_reader(input.as_bytes()).unwrap();
+        assert_eq!(npis, vec!["1043566623", "1972767655", "1487648176"]);
+
+        let input = "1043566623\n\n104356662X\n";
+        let message = read_npis_from_reader(input.as_bytes()).unwrap_err().to_string();
+        assert!(message.contains("Line 3"));
+        assert!(message.contains("104356662X"));
+    }
+
+    #[test]
+    fn test_data_preserves_response_order() {
+        let rate = r#"[{
+            "code": "99214",
+            "codeType": "CPT",
+            "negotiatedType": "negotiated",
+            "minRate": 65.87,
+            "maxRate": 266.88,
+            "avgRate": 147.03,
+            "instances": 6
+        }]"#;
+        let npis = ["1972767655", "1043566623", "1487648176", "1234567893"];
+        let data = npis
+            .iter()
+            .map(|npi| format!(r#""{}": {}"#, npi, rate))
+            .collect::<Vec<_>>()
+            .join(",");
+        let json = format!(
+            r#"{{
+                "data": {{ {} }},
+                "meta": {{
+                    "planId": "942404110",
+                    "payer": "UNH",
+                    "requestId": "req_test123",
+                    "timestamp": "2025-06-15T23:15:48.734729Z",
+                    "processingTimeMs": 912,
+                    "inNetworkRecordsCount": 14
+                }}
+            }}"#,
+            data
+        );
+
+        let response: PricingResponse = serde_json::from_str(&json).unwrap();
+        let keys: Vec<&str> = response.data.keys().map(String::as_str).collect();
+        assert_eq!(keys, npis);
+
+        let best = response.best_rate_per_npi();
+        let keys: Vec<&str> = best.keys().map(String::as_str).collect();
+        assert_eq!(keys, npis);
+    }
+
+    #[test]
+    fn test_anonymized() {
+        let response = sample_pricing_response();
+        let anonymized = response.anonymized();
+
+        let mut keys: Vec<&str> = anonymized.data.keys().map(String::as_str).collect();
+        keys.sort();
+        assert_eq!(keys, ["NPI_1", "NPI_2"]);
+        assert!(!format!("{:?}", anonymized).contains("1043566623"));
+
+        let original = &response.data["1043566623"];
+        let masked = &anonymized.data["NPI_1"];
+        assert_eq!(masked.len(), original.len());
+        for (masked, original) in masked.iter().zip(original) {
+            assert_eq!(masked.avg_rate, original.avg_rate);
+            assert_eq!(masked.min_rate, original.min_rate);
+            assert_eq!(masked.max_rate, original.max_rate);
+            assert_eq!(masked.instances, original.instances);
+        }
+        assert_eq!(anonymized.data["NPI_2"][0].avg_rate, 150.25);
+
+        assert_eq!(anonymized.meta.plan_id, "");
+        assert_eq!(anonymized.meta.payer, "");
+        assert_eq!(anonymized.meta.request_id, "req_test123");
+        assert_eq!(
+            anonymized.meta.in_network_records_count,
+            response.meta.in_network_records_count
+        );
+    }
+
+    /// Two pages that both contain NPI 1043566623's negotiated 99214 rate
+    fn conflicting_pages() -> Vec<PricingResponse> {
+        let first = sample_pricing_response();
+
+        let mut second = sample_pricing_response();
+        second.data.retain(|npi, _| npi == "1043566623");
+        let rates = second.data.get_mut("1043566623").unwrap();
+        rates.truncate(1);
+        rates[0].min_rate = 75.87;
+        rates[0].max_rate = 276.88;
+        rates[0].avg_rate = 153.03;
+        rates[0].instances = 10;
+        let rates = rates.clone();
+        second.data.insert("1487648176".to_string(), rates);
+
+        vec![first, second]
+    }
+
+    #[test]
+    fn test_merge_all_keep_first() {
+        let merged = PricingResponse::merge_all(conflicting_pages(), ConflictStrategy::KeepFirst);
+
+        assert_eq!(merged.data.len(), 3);
+        let rates = &merged.data["1043566623"];
+        assert_eq!(rates.len(), 2);
+        assert_eq!(rates[0].avg_rate, 147.03);
+        assert_eq!(rates[0].instances, 6);
+        assert_eq!(rates[1].negotiated_type, "fee schedule");
+        assert_eq!(merged.data["1487648176"][0].avg_rate, 153.03);
+        assert_eq!(merged.meta.request_id, "req_test123");
+    }
+
+    #[test]
+    fn test_merge_all_keep_highest_instances() {
+        let merged = PricingResponse::merge_all(
+            conflicting_pages(),
+            ConflictStrategy::KeepHighestInstances,
+        );
+
+        let rates = &merged.data["1043566623"];
+        assert_eq!(rates.len(), 2);
+        assert_eq!(rates[0].avg_rate, 153.03);
+        assert_eq!(rates[0].instances, 10);
+        assert_eq!(rates[1].avg_rate, 100.0);
+    }
+
+    #[test]
+    fn test_merge_all_average() {
+        let merged = PricingResponse::merge_all(conflicting_pages(), ConflictStrategy::Average);
+
+        let rates = &merged.data["1043566623"];
+        assert_eq!(rates.len(), 2);
+        assert!((rates[0].min_rate - 70.87).abs() < 1e-9);
+        assert!((rates[0].max_rate - 271.88).abs() < 1e-9);
+        assert!((rates[0].avg_rate - 150.03).abs() < 1e-9);
+        assert_eq!(rates[0].instances, 16);
+        assert_eq!(merged.data["1972767655"][0].avg_rate, 150.25);
+
+        let mut pages = conflicting_pages();
+        for page in &mut pages {
+            for rate in page.data.values_mut().flatten() {
+                rate.instances = u32::MAX;
+            }
+        }
+        let merged = PricingResponse::merge_all(pages, ConflictStrategy::Average);
+        assert_eq!(merged.data["1043566623"][0].instances, u32::MAX);
+
+        let empty = PricingResponse::merge_all(Vec::new(), ConflictStrategy::Average);
+        assert!(empty.data.is_empty());
+    }
+
+    #[test]
+    fn test_split_by_npi() {
+        let mut parts = sample_pricing_response().split_by_npi();
+        parts.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(parts.len(), 2);
+
+        let (npi, response) = &parts[0];
+        assert_eq!(npi, "1043566623");
+        assert_eq!(response.data.len(), 1);
+        assert_eq!(response.data["1043566623"].len(), 2);
+        assert_eq!(response.meta.request_id, "req_test123");
+
+        let (npi, response) = &parts[1];
+        assert_eq!(npi, "1972767655");
+        assert_eq!(response.data.len(), 1);
+        assert_eq!(response.data["1972767655"][0].avg_rate, 150.25);
+        assert_eq!(response.meta.plan_id, "942404110");
+    }
+
+    #[test]
+    fn test_total_instances() {
+        let mut response = sample_pricing_response();
+        assert_eq!(response.total_instances(), 6 + 2 + 4);
+
+        response.data.get_mut("1972767655").unwrap()[0].instances = u32::MAX;
+        assert_eq!(response.total_instances(), 8 + u64::from(u32::MAX));
+
+        response.data.clear();
+        assert_eq!(response.total_instances(), 0);
+    }
+
+    #[test]
+    fn test_is_suspicious() {
+        let mut response = sample_pricing_response();
+        assert!(!response.is_suspicious());
+
+        response.data.clear();
+        assert!(response.is_suspicious());
+
+        response.meta.in_network_records_count = 0;
+        assert!(!response.is_suspicious());
+    }
+
+    #[test]
+    fn test_per_instance_confidence_weight() {
+        let mut rate = sample_pricing_response().data["1043566623"][0].clone();
+        let mut weight_at = |instances| {
+            rate.instances = instances;
+            rate.per_instance_confidence_weight()
+        };
+
+        let none = weight_at(0);
+        let few = weight_at(5);
+        let many = weight_at(1000);
+
+        assert_eq!(none, 0.0);
+        assert!((few - 5.0 / 15.0).abs() < 1e-12);
+        assert_eq!(weight_at(10), 0.5);
+        assert!(none < few && few < many);
+        assert!(many > 0.99 && many < 1.0);
+    }
+
+    #[test]
+    fn test_pricing_response_serialize_round_trip() {
+        let response = sample_pricing_response();
+        let json = serde_json::to_value(&response).unwrap();
+
+        assert_eq!(json["meta"]["planId"], "942404110");
+        assert_eq!(json["meta"]["payer"], "UNH");
+        assert_eq!(json["meta"]["requestId"], "req_test123");
+        assert_eq!(json["meta"]["timestamp"], "2025-06-15T23:15:48.734729Z");
+        assert_eq!(json["meta"]["inNetworkRecordsCount"], 14);
+        assert_eq!(json["data"]["1043566623"][0]["avgRate"], 147.03);
+        assert_eq!(json["data"]["1043566623"][0]["negotiatedType"], "negotiated");
+        assert!(json["data"]["1043566623"][0].get("modifier").is_none());
+        assert!(json.get("requestedPlanId").is_none() && json.get("requested_plan_id").is_none());
+
+        let parsed: PricingResponse = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.data.len(), response.data.len());
+        assert_eq!(parsed.meta.timestamp, response.meta.timestamp);
+        assert_eq!(parsed.data["1972767655"][0].avg_rate, 150.25);
+    }
+
+    #[test]
+    fn test_likelihood_response_serialize_round_trip() {
+        let response: LikelihoodResponse = serde_json::from_value(serde_json::json!({
+            "data": {
+                "1487648176": { "code": "99214", "codeType": "CPT", "likelihood": 0.9 }
+            },
+            "meta": {
+                "requestId": "req_test456",
+                "timestamp": "not a timestamp",
+                "processingTimeMs": 731,
+                "outOfNetworkRecordsCount": 68
+            }
+        }))
+        .unwrap();
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["data"]["1487648176"]["codeType"], "CPT");
+        assert_eq!(json["data"]["1487648176"]["likelihood"], 0.9);
+        assert_eq!(json["meta"]["outOfNetworkRecordsCount"], 68);
+        // Unparseable timestamps survive verbatim
+        assert_eq!(json["meta"]["timestamp"], "not a timestamp");
+    }
+
+    #[test]
+    fn test_null_npi_entries_are_dropped() {
+        let mut json = serde_json::to_value(sample_pricing_response()).unwrap();
+        json["data"]["1487648176"] = serde_json::Value::Null;
+        json["data"]["1234567893"] = serde_json::json!([]);
+        let response: PricingResponse = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(response.data.len(), 3);
+        assert!(response.rates_for("1487648176").is_none());
+        assert!(response.rates_for("1234567893").unwrap().is_empty());
+        assert_eq!(response.rates_for("1043566623").unwrap()[0].avg_rate, 147.03);
+
+        let lenient: LenientPricingResponse = serde_json::from_value(json.clone()).unwrap();
+        let response = PricingResponse::from(lenient);
+        assert_eq!(response.data.len(), 3);
+        assert!(response.parse_errors.is_empty());
+
+        // Both modes reject a body without `data`
+        json.as_object_mut().unwrap().remove("data");
+        assert!(serde_json::from_value::<PricingResponse>(json.clone()).is_err());
+        assert!(serde_json::from_value::<LenientPricingResponse>(json).is_err());
+
+        let response: LikelihoodResponse = serde_json::from_value(serde_json::json!({
+            "data": {
+                "1487648176": { "code": "99214", "codeType": "CPT", "likelihood": 0.9 },
+                "1234567893": null
+            },
+            "meta": {
+                "requestId": "req_test456",
+                "timestamp": "2025-06-15T23:22:22.395111Z",
+                "processingTimeMs": 731,
+                "outOfNetworkRecordsCount": 68
+            }
+        }))
+        .unwrap();
+        assert_eq!(response.npis().collect::<Vec<_>>(), ["1487648176"]);
+        assert!(response.score_for("1234567893").is_none());
+    }
+
+    #[test]
+    fn test_response_equality() {
+        let response = sample_pricing_response();
+        assert_eq!(sample_pricing_response(), response);
+
+        let mut changed = sample_pricing_response();
+        changed.data.get_mut("1972767655").unwrap()[0].avg_rate += 0.001;
+        assert_ne!(changed, response);
+        let rate = &response.rates_for("1972767655").unwrap()[0];
+        let nudged = &changed.rates_for("1972767655").unwrap()[0];
+        assert!(nudged.approx_eq(rate, RateData::RATE_EPSILON));
+        assert!(!nudged.approx_eq(rate, 0.0001));
+
+        let mut renamed = rate.clone();
+        renamed.negotiated_type = "fee schedule".to_string();
+        assert!(!renamed.approx_eq(rate, 1.0));
+        let mut nan = rate.clone();
+        nan.min_rate = f64::NAN;
+        assert!(!nan.approx_eq(&nan, 1.0));
+
+        let json = serde_json::json!({
+            "data": {
+                "1487648176": { "code": "99214", "codeType": "CPT", "likelihood": 0.9 }
+            },
+            "meta": {
+                "requestId": "req_test456",
+                "timestamp": "2025-06-15T23:22:22.395111Z",
+                "processingTimeMs": 731,
+                "outOfNetworkRecordsCount": 1
+            }
+        });
+        let likelihood: LikelihoodResponse = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(likelihood, serde_json::from_value(json).unwrap());
+    }
+
+    #[test]
+    fn test_unknown_top_level_fields_are_kept() {
+        let mut json = serde_json::to_value(sample_pricing_response()).unwrap();
+        json["warnings"] = serde_json::json!(["plan data is 30 days old"]);
+        json["version"] = serde_json::json!(2);
+
+        let response: PricingResponse = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(response.extra.len(), 2);
+        assert_eq!(response.extra["version"], 2);
+        assert_eq!(response.rates_for("1972767655").unwrap()[0].avg_rate, 150.25);
+        // Written back out alongside the modeled fields
+        assert_eq!(serde_json::to_value(&response).unwrap(), json);
+
+        let lenient: LenientPricingResponse = serde_json::from_value(json).unwrap();
+        let response = PricingResponse::from(lenient);
+        assert_eq!(response.extra["warnings"][0], "plan data is 30 days old");
+
+        let response: LikelihoodResponse = serde_json::from_value(serde_json::json!({
+            "data": {},
+            "meta": {
+                "requestId": "req_test456",
+                "timestamp": "2025-06-15T23:22:22.395111Z",
+                "processingTimeMs": 731,
+                "outOfNetworkRecordsCount": 68
+            },
+            "model": "v3"
+        }))
+        .unwrap();
+        assert_eq!(response.extra["model"], "v3");
+        assert!(!response.extra.contains_key("meta"));
+    }
+
+    #[test]
+    fn test_summary_accessors() {
+        let rate = |avg_rate: f64| RateData {
+            code: "99214".to_string(),
+            code_type: "CPT".to_string(),
+            negotiated_type: "negotiated".to_string(),
+            min_rate: avg_rate,
+            max_rate: avg_rate,
+            avg_rate,
+            instances: 1,
+            modifier: None,
+        };
+        let mut response = PricingResponse {
+            data: NpiMap::from_iter([
+                ("1043566623".to_string(), vec![rate(150.0), rate(90.0)]),
+                ("1972767655".to_string(), vec![rate(120.0)]),
+                ("1487648176".to_string(), vec![rate(200.0), rate(f64::NAN)]),
+                ("1234567893".to_string(), vec![]),
+            ]),
+            ..PricingResponse::default()
+        };
+
+        // Multi-rate NPIs compete with their cheapest and most expensive rates
+        let (npi, cheapest) = response.cheapest_npi().unwrap();
+        assert_eq!((npi, cheapest.avg_rate), ("1043566623", 90.0));
+        let (npi, priciest) = response.most_expensive_npi().unwrap();
+        assert_eq!((npi, priciest.avg_rate), ("1487648176", 200.0));
+        assert_eq!(response.overall_avg_rate(), Some(140.0));
+
+        // Ties go to the lowest NPI
+        response.data.insert("1000000004".to_string(), vec![rate(90.0)]);
+        assert_eq!(response.cheapest_npi().unwrap().0, "1000000004");
+
+        let empty = PricingResponse::default();
+        assert!(empty.cheapest_npi().is_none());
+        assert!(empty.most_expensive_npi().is_none());
+        assert_eq!(empty.overall_avg_rate(), None);
+    }
+
+    #[test]
+    fn test_rate_consistency() {
+        let rate = sample_pricing_response().data["1043566623"][0].clone();
+        assert!(rate.is_consistent());
+
+        let mut below_min = rate.clone();
+        below_min.avg_rate = rate.min_rate - 1.0;
+        assert!(!below_min.is_consistent());
+
+        // Rounding noise within the epsilon is tolerated
+        let mut rounded = rate.clone();
+        rounded.avg_rate = rate.max_rate + RateData::RATE_EPSILON / 2.0;
+        assert!(rounded.is_consistent());
+
+        let mut nan = rate;
+        nan.avg_rate = f64::NAN;
+        assert!(!nan.is_consistent());
+    }
+
+    #[test]
+    fn test_meta_age() {
+        let meta = sample_pricing_response().meta;
+        let now = "2025-06-15T23:20:48.734729Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(meta.age_at(now), Some(chrono::Duration::minutes(5)));
+
+        let skewed = "2025-06-15T23:15:00Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(meta.age_at(skewed), Some(chrono::Duration::zero()));
+
+        let meta = LikelihoodMeta {
+            request_id: "req_test456".to_string(),
+            timestamp: Some("2025-06-15T23:22:22Z".parse().unwrap()),
+            timestamp_raw: None,
+            processing_time_ms: 731,
+            out_of_network_records_count: 68,
+        };
+        let now = "2025-06-15T23:22:52Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(meta.age_at(now), Some(chrono::Duration::seconds(30)));
+        assert!(meta.age().unwrap() > chrono::Duration::zero());
+
+        let meta = LikelihoodMeta { timestamp: None, ..meta };
+        assert_eq!(meta.age_at(now), None);
+    }
+
+    #[test]
+    fn test_malformed_timestamp_keeps_data() {
+        let json = r#"{
+            "data": {
+                "1043566623": [{
+                    "code": "99214",
+                    "codeType": "CPT",
+                    "negotiatedType": "negotiated",
+                    "minRate": 65.87,
+                    "maxRate": 266.88,
+                    "avgRate": 147.03,
+                    "instances": 6
+                }]
+            },
+            "meta": {
+                "planId": "942404110",
+                "payer": "UNH",
+                "requestId": "req_test123",
+                "timestamp": "last tuesday",
+                "processingTimeMs": 912,
+                "inNetworkRecordsCount": 14
+            }
+        }"#;
+
+        let response: PricingResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.data["1043566623"][0].avg_rate, 147.03);
+        assert_eq!(response.meta.timestamp, None);
+        assert_eq!(response.meta.timestamp_raw.as_deref(), Some("last tuesday"));
+        assert_eq!(response.meta.age(), None);
+
+        let meta = sample_pricing_response().meta;
+        assert!(meta.timestamp.is_some());
+        assert_eq!(
+            meta.timestamp_raw.as_deref(),
+            Some("2025-06-15T23:15:48.734729Z")
+        );
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_load_pricing_requests_yaml() {
+        let yaml = r#"
+- npis: ["1043566623", "1972767655"]
+  conditionCode: "99214"
+  planId: "942404110"
+- npis:
+    - "1487648176"
+  conditionCode: "0470"
+  codeType: MS-DRG
+"#;
+
+        let requests = load_pricing_requests_yaml(yaml).unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].npis, vec!["1043566623", "1972767655"]);
+        assert_eq!(requests[0].plan_id.as_deref(), Some("942404110"));
+        assert_eq!(requests[0].code_type, None);
+        assert_eq!(requests[1].code_type, Some(CodeType::MsDrg));
+        assert!(requests.iter().all(|request| request.validate().is_ok()));
+
+        let invalid = r#"
+- npis: ["1043566623"]
+  conditionCode: "99214"
+- npis: ["12345"]
+  conditionCode: "99214"
+"#;
+        let message = load_pricing_requests_yaml(invalid).unwrap_err().to_string();
+        assert!(message.contains("Request 1"));
+        assert!(message.contains("Invalid NPI format"));
+
+        let malformed = load_pricing_requests_yaml("- npis: [").unwrap_err();
+        assert!(matches!(malformed, crate::DocarooError::ParseError { .. }));
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn test_write_csv() {
+        let response = sample_pricing_response();
+
+        let mut buffer = Vec::new();
+        response.write_csv(&mut buffer).unwrap();
+
+        let mut reader = csv::Reader::from_reader(buffer.as_slice());
+        let headers = reader.headers().unwrap().clone();
+        assert_eq!(&headers[0], "npi");
+        assert_eq!(&headers[8], "modifier");
+        assert_eq!(&headers[10], "payer");
+
+        let rows: Vec<csv::StringRecord> = reader.records().map(|r| r.unwrap()).collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(&rows[0][0], "1043566623");
+        assert_eq!(&rows[1][3], "fee schedule");
+        assert_eq!(&rows[2][6], "150.25");
+        assert_eq!(&rows[2][8], "");
+        assert_eq!(&rows[2][9], "req_test123");
+    }
 }
\ No newline at end of file
@@ -0,0 +1,142 @@
+//! Plan-to-payer mapping learned from pricing responses
+
+use crate::models::{Payer, PricingResponse};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+/// Cache mapping plan IDs to the payer that offers them
+///
+/// Every pricing response names its plan and payer in `meta`, so a
+/// [`DocarooClient`](crate::DocarooClient) records each pairing it sees; query it with
+/// [`DocarooClient::known_payer`](crate::DocarooClient::known_payer). Clones share the
+/// same map, so a cache can be preloaded and handed to several clients through
+/// [`DocarooConfig::payer_cache`](crate::client::DocarooConfig).
+///
+/// # Example
+///
+/// ```
+/// use docaroo_rs::{DocarooClient, client::DocarooConfig, payers::PayerCache};
+///
+/// let cache = PayerCache::new();
+/// cache.insert("942404110", "UNH");
+///
+/// let client = DocarooClient::with_config(
+///     DocarooConfig::builder()
+///         .api_key("your-api-key")
+///         .payer_cache(cache)
+///         .build(),
+/// );
+/// assert_eq!(client.known_payer("942404110").unwrap(), "UNH");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PayerCache {
+    payers: Arc<RwLock<HashMap<String, Payer>>>,
+}
+
+impl PayerCache {
+    /// Create an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that `plan_id` belongs to `payer`, replacing any earlier pairing
+    pub fn insert(&self, plan_id: impl Into<String>, payer: impl Into<Payer>) {
+        self.payers
+            .write()
+            .unwrap()
+            .insert(plan_id.into(), payer.into());
+    }
+
+    /// Look up the payer for a plan
+    pub fn get(&self, plan_id: &str) -> Option<Payer> {
+        self.payers.read().unwrap().get(plan_id).cloned()
+    }
+
+    /// Number of plans with a known payer
+    pub fn len(&self) -> usize {
+        self.payers.read().unwrap().len()
+    }
+
+    /// Check whether no pairings have been recorded
+    pub fn is_empty(&self) -> bool {
+        self.payers.read().unwrap().is_empty()
+    }
+
+    /// Copy of every recorded pairing, e.g. for persisting between runs
+    pub fn snapshot(&self) -> HashMap<String, Payer> {
+        self.payers.read().unwrap().clone()
+    }
+
+    /// Record the plan and payer named in a pricing response's metadata
+    ///
+    /// Responses with an empty plan ID or payer are ignored.
+    pub fn observe(&self, response: &PricingResponse) {
+        let meta = &response.meta;
+        if !meta.plan_id.is_empty() && !meta.payer.is_empty() {
+            self.insert(meta.plan_id.clone(), meta.payer.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DocarooClient, client::DocarooConfig, models::PricingRequest};
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
+
+    #[test]
+    fn test_observe_skips_missing_meta() {
+        let cache = PayerCache::new();
+        cache.observe(&PricingResponse::default());
+        assert!(cache.is_empty());
+
+        let mut response = PricingResponse::default();
+        response.meta.plan_id = "942404110".to_string();
+        response.meta.payer = Payer::new("UNH");
+        cache.clone().observe(&response);
+        assert_eq!(cache.snapshot()["942404110"], "UNH");
+    }
+
+    #[tokio::test]
+    async fn test_known_payer_after_pricing_call() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {},
+                "meta": {
+                    "planId": "942404110",
+                    "payer": "UNH",
+                    "requestId": "req_payer",
+                    "timestamp": "2025-06-15T23:15:48.734729Z",
+                    "processingTimeMs": 12,
+                    "inNetworkRecordsCount": 0
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let client = DocarooClient::with_config(
+            DocarooConfig::builder()
+                .api_key("test-key")
+                .base_url(server.uri())
+                .build(),
+        );
+        assert!(client.known_payer("942404110").is_none());
+
+        let request = PricingRequest::builder()
+            .npis(vec!["1043566623".to_string()])
+            .condition_code("99214")
+            .build();
+        client.pricing().get_in_network_rates(request).await.unwrap();
+
+        assert_eq!(client.known_payer("942404110").unwrap(), "UNH");
+        assert!(client.known_payer("131624096").is_none());
+        assert_eq!(client.payer_cache().len(), 1);
+    }
+}
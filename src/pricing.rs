@@ -2,9 +2,149 @@
 
 use crate::{
     client::DocarooClient,
-    error::Result,
-    models::{PricingRequest, PricingResponse},
+    error::{DocarooError, Result},
+    models::{
+        CodeType, ConflictStrategy, LenientPricingResponse, PricingRequest, PricingResponse,
+        RateData,
+    },
 };
+use bon::Builder;
+use futures::{StreamExt, TryStreamExt};
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+use tokio_util::sync::CancellationToken;
+
+/// Maximum number of NPIs the API accepts in a single pricing request
+pub const MAX_NPIS_PER_PRICING_REQUEST: usize = 10;
+
+/// Options for [`PricingClient::get_in_network_rates_bulk`] and
+/// [`PricingClient::price_codes`]
+#[derive(Debug, Clone, Builder)]
+pub struct BulkOptions {
+    /// Maximum number of requests in flight at once (defaults to 4)
+    #[builder(default = 4)]
+    pub concurrency: usize,
+
+    /// Return the first error instead of collecting failures (defaults to true)
+    #[builder(default = true)]
+    pub fail_fast: bool,
+
+    /// Token that abandons the lookup when cancelled (defaults to none)
+    ///
+    /// Once it is cancelled, no further requests are issued, those in flight are
+    /// aborted and [`DocarooError::Cancelled`] is returned, discarding any results
+    /// already received.
+    pub cancellation: Option<CancellationToken>,
+}
+
+impl Default for BulkOptions {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// Merged result of a chunked pricing lookup
+#[derive(Debug)]
+pub struct BulkPricingResponse {
+    /// Rates for every successful chunk merged into one response
+    ///
+    /// `meta` comes from the first successful chunk, except that `processing_time_ms`
+    /// and `in_network_records_count` are summed across chunks.
+    pub response: PricingResponse,
+    /// Request ID of every successful chunk, in chunk order
+    pub request_ids: Vec<String>,
+    /// Chunks that failed, when [`BulkOptions::fail_fast`] is off
+    pub failures: Vec<ChunkFailure>,
+}
+
+/// A chunk of a bulk pricing lookup that failed
+#[derive(Debug)]
+pub struct ChunkFailure {
+    /// NPIs in the failed chunk
+    pub npis: Vec<String>,
+    /// Error returned for the chunk
+    pub error: DocarooError,
+}
+
+/// Pricing results for the NPIs that succeeded, alongside the errors for those that
+/// did not
+///
+/// Returned by [`PricingClient::get_in_network_rates_partial`].
+#[derive(Debug)]
+pub struct PartialResponse {
+    /// Rates for every NPI that succeeded
+    pub response: PricingResponse,
+    /// Error for each NPI that failed on its own, keyed by NPI
+    pub failures: HashMap<String, DocarooError>,
+}
+
+impl PartialResponse {
+    /// Check whether every NPI succeeded
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Pricing responses for several billing codes, keyed by code
+///
+/// Returned by [`PricingClient::price_codes`].
+#[derive(Debug)]
+pub struct CodePricing {
+    /// Response for each code that succeeded
+    pub responses: HashMap<String, PricingResponse>,
+    /// Error for each code that failed, when [`BulkOptions::fail_fast`] is off
+    pub failures: HashMap<String, DocarooError>,
+}
+
+impl CodePricing {
+    /// Check whether every code succeeded
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// One rate per NPI and code, keyed by NPI and then by the requested code
+    ///
+    /// Where an NPI has several rates for a code, the one backed by the most instances
+    /// is kept, as in [`PricingResponse::best_rate_per_npi`]. A code that returned no
+    /// rates for an NPI is absent from that NPI's map, and an NPI with no rates for
+    /// any code is absent altogether. Failed codes contribute nothing; check
+    /// [`failures`](Self::failures) to tell them apart from codes without rates.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use docaroo_rs::{DocarooClient, models::CodeType, pricing::BulkOptions};
+    /// # async fn example(npis: Vec<String>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DocarooClient::new("your-api-key");
+    ///
+    /// let codes = [
+    ///     ("99213".to_string(), CodeType::Cpt),
+    ///     ("99214".to_string(), CodeType::Cpt),
+    /// ];
+    /// let priced = client.pricing().price_codes(&npis, &codes, BulkOptions::default()).await?;
+    /// for (npi, rates) in priced.rates_by_npi() {
+    ///     for (code, rate) in rates {
+    ///         println!("{npi} {code}: ${:.2}", rate.avg_rate);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn rates_by_npi(&self) -> HashMap<String, HashMap<String, RateData>> {
+        let mut rates: HashMap<String, HashMap<String, RateData>> = HashMap::new();
+        for (code, response) in &self.responses {
+            for (npi, rate) in response.best_rate_per_npi() {
+                rates
+                    .entry(npi)
+                    .or_default()
+                    .insert(code.clone(), rate.clone());
+            }
+        }
+        rates
+    }
+}
 
 /// Client for pricing-related operations
 #[derive(Debug, Clone)]
@@ -38,6 +178,10 @@ impl PricingClient {
     /// - Authentication fails (invalid API key)
     /// - Rate limits are exceeded
     /// - The API returns an error response
+    /// - A configured [`plan_payer_validator`](crate::client::DocarooConfig) rejects the
+    ///   response's plan and payer
+    /// - `error_on_suspicious` is set and the response is
+    ///   [suspicious](PricingResponse::is_suspicious)
     ///
     /// # Example
     ///
@@ -67,66 +211,1364 @@ impl PricingClient {
     /// # }
     /// ```
     pub async fn get_in_network_rates(&self, request: PricingRequest) -> Result<PricingResponse> {
+        self.fetch_rates(request, None).await
+    }
+
+    /// Get in-network rates with a timeout for this call only
+    ///
+    /// Like [`get_in_network_rates`](Self::get_in_network_rates), but `timeout`
+    /// replaces [`DocarooConfig::timeout`](crate::client::DocarooConfig) for each
+    /// attempt, e.g. a short timeout for interactive lookups or a long one for slow
+    /// bulk queries. Exceeding it returns [`DocarooError::Timeout`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use docaroo_rs::{DocarooClient, models::PricingRequest};
+    /// # use std::time::Duration;
+    /// # async fn example(request: PricingRequest) -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DocarooClient::new("your-api-key");
+    /// let response = client
+    ///     .pricing()
+    ///     .get_in_network_rates_with_timeout(request, Duration::from_secs(5))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_in_network_rates_with_timeout(
+        &self,
+        request: PricingRequest,
+        timeout: Duration,
+    ) -> Result<PricingResponse> {
+        self.fetch_rates(request, Some(timeout)).await
+    }
+
+    /// Validate, send and check a pricing request
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "docaroo.pricing",
+            skip_all,
+            fields(
+                endpoint = "/pricing/in-network",
+                npi_count = request.npis.len(),
+                code_type = request.code_type.as_ref().map(CodeType::as_str),
+                status = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+                processing_time_ms = tracing::field::Empty,
+            ),
+        )
+    )]
+    async fn fetch_rates(
+        &self,
+        request: PricingRequest,
+        timeout: Option<Duration>,
+    ) -> Result<PricingResponse> {
+        let mut request = request;
+        self.client.normalize_pricing_request(&mut request);
+
         // Validate request
         self.validate_pricing_request(&request)?;
+        self.client.check_code_type_supported(request.code_type).await?;
 
-        // Build URL
-        let url = self.client.build_url("/pricing/in-network")?;
-
-        // Send request
-        let response = self
+        let cache = self
             .client
-            .http_client()
-            .post(url)
-            .json(&request)
-            .send()
-            .await?;
+            .config()
+            .response_cache
+            .clone()
+            .map(|cache| (cache, crate::cache::cache_key(&request)));
+        if let Some((cache, key)) = &cache {
+            if let Some(response) = cache.get(key).await {
+                return Ok(response);
+            }
+        }
+
+        let requested_plan_id = request.plan_id.clone();
+
+        // Send request, retrying according to the configured policy
+        let method = self.client.config().pricing_method;
+        let endpoint = "/pricing/in-network";
+        let mut response: PricingResponse = if self.client.config().lenient_parsing {
+            self.client
+                .send_json::<_, LenientPricingResponse>(method, endpoint, &request, timeout)
+                .await?
+                .into()
+        } else {
+            self.client
+                .send_json(method, endpoint, &request, timeout)
+                .await?
+        };
+        self.client.record_processing_time(response.meta.processing_time_ms);
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::Span::current();
+            span.record("request_id", response.meta.request_id.as_str());
+            span.record("processing_time_ms", response.meta.processing_time_ms);
+        }
+        self.client.check_pricing_response(&response)?;
+        self.client.payer_cache().observe(&response);
+        response.requested_plan_id = requested_plan_id;
+
+        if let Some((cache, key)) = cache {
+            let ttl = self.client.config().cache_ttl;
+            cache.insert(key, response.clone(), ttl).await;
+        }
 
-        // Handle response
-        DocarooClient::handle_response(response).await
+        Ok(response)
     }
 
-    /// Validate a pricing request before sending
-    fn validate_pricing_request(&self, request: &PricingRequest) -> Result<()> {
-        use crate::error::DocarooError;
+    /// Get in-network rates, returning `None` when the API found no rates
+    ///
+    /// Behaves like [`get_in_network_rates`](Self::get_in_network_rates), but a
+    /// well-formed response with empty `data` becomes `Ok(None)`, so "nothing found"
+    /// can be handled separately from real errors.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use docaroo_rs::{DocarooClient, models::PricingRequest};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DocarooClient::new("your-api-key");
+    ///
+    /// let request = PricingRequest::builder()
+    ///     .npis(vec!["1043566623".to_string()])
+    ///     .condition_code("99214")
+    ///     .build();
+    ///
+    /// match client.pricing().get_in_network_rates_opt(request).await? {
+    ///     Some(response) => println!("{} providers priced", response.data.len()),
+    ///     None => println!("No rates found"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_in_network_rates_opt(
+        &self,
+        request: PricingRequest,
+    ) -> Result<Option<PricingResponse>> {
+        let response = self.get_in_network_rates(request).await?;
+        Ok((!response.data.is_empty()).then_some(response))
+    }
 
-        // Validate NPIs count
-        if request.npis.is_empty() {
-            return Err(DocarooError::InvalidRequest(
-                "At least one NPI must be provided".to_string(),
-            ));
+    /// Get in-network rates for any number of NPIs
+    ///
+    /// Duplicate NPIs are dropped and the rest are split into chunks of
+    /// [`MAX_NPIS_PER_PRICING_REQUEST`], which are sent with at most
+    /// `options.concurrency` requests in flight and merged into a single response.
+    /// Every chunk is validated before any request is sent.
+    ///
+    /// # Errors
+    ///
+    /// With `options.fail_fast` set, returns the first chunk error. Otherwise failed
+    /// chunks are reported in [`BulkPricingResponse::failures`], and an error is only
+    /// returned if every chunk failed.
+    /// Returns [`DocarooError::Cancelled`] if [`BulkOptions::cancellation`] is
+    /// cancelled first.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use docaroo_rs::{DocarooClient, models::PricingRequest, pricing::BulkOptions};
+    /// # async fn example(npis: Vec<String>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DocarooClient::new("your-api-key");
+    ///
+    /// let request = PricingRequest::builder()
+    ///     .npis(npis) // e.g. 200 NPIs
+    ///     .condition_code("99214")
+    ///     .build();
+    /// let options = BulkOptions::builder().concurrency(8).fail_fast(false).build();
+    ///
+    /// let bulk = client.pricing().get_in_network_rates_bulk(request, options).await?;
+    /// println!("{} providers priced", bulk.response.data.len());
+    /// for failure in &bulk.failures {
+    ///     eprintln!("{:?}: {}", failure.npis, failure.error);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_in_network_rates_bulk(
+        &self,
+        request: PricingRequest,
+        options: BulkOptions,
+    ) -> Result<BulkPricingResponse> {
+        self.bulk_in_chunks(request, MAX_NPIS_PER_PRICING_REQUEST, options)
+            .await
+    }
+
+    /// Get in-network rates, keeping the NPIs that succeed when others fail
+    ///
+    /// The request is first sent as a whole. If that fails with an error one NPI may
+    /// have caused ([`DocarooError::InvalidRequest`] or [`DocarooError::ApiError`]),
+    /// each NPI is retried in its own request, so one problematic provider does not
+    /// cost the rates for the rest.
+    ///
+    /// - If the whole request succeeds, `failures` is empty and only one request is sent.
+    /// - If some NPIs fail on their own, their errors are returned in `failures` and
+    ///   the other NPIs' rates in `response`.
+    /// - If every NPI fails on its own, the first NPI's error is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request is invalid, fails for a reason unrelated to
+    /// individual NPIs (authentication, rate limits, server errors, ...), or fails
+    /// for every NPI.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use docaroo_rs::{DocarooClient, models::PricingRequest};
+    /// # async fn example(request: PricingRequest) -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DocarooClient::new("your-api-key");
+    ///
+    /// let partial = client.pricing().get_in_network_rates_partial(request).await?;
+    /// for (npi, error) in &partial.failures {
+    ///     eprintln!("{npi}: {error}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_in_network_rates_partial(
+        &self,
+        request: PricingRequest,
+    ) -> Result<PartialResponse> {
+        self.get_in_network_rates_partial_with_cancellation(request, None)
+            .await
+    }
+
+    /// Get in-network rates keeping the NPIs that succeed, abandoning them on
+    /// cancellation
+    ///
+    /// Behaves like [`get_in_network_rates_partial`](Self::get_in_network_rates_partial),
+    /// except that cancelling `cancellation` stops the lookup: no further requests are
+    /// issued, those in flight are aborted and [`DocarooError::Cancelled`] is returned.
+    /// A token that is already cancelled fails before anything is sent.
+    pub async fn get_in_network_rates_partial_with_cancellation(
+        &self,
+        request: PricingRequest,
+        cancellation: Option<CancellationToken>,
+    ) -> Result<PartialResponse> {
+        until_cancelled(cancellation.as_ref(), self.partial_inner(request)).await
+    }
+
+    async fn partial_inner(&self, request: PricingRequest) -> Result<PartialResponse> {
+        let error = match self.get_in_network_rates(request.clone()).await {
+            Ok(response) => {
+                return Ok(PartialResponse {
+                    response,
+                    failures: HashMap::new(),
+                });
+            }
+            Err(error) => error,
+        };
+
+        let npi_specific = matches!(
+            error,
+            DocarooError::InvalidRequest(_) | DocarooError::ApiError { .. }
+        );
+        if !npi_specific || request.npis.len() < 2 {
+            return Err(error);
         }
 
-        if request.npis.len() > 10 {
+        let options = BulkOptions::builder().fail_fast(false).build();
+        let bulk = self.bulk_in_chunks(request, 1, options).await?;
+        let failures = bulk
+            .failures
+            .into_iter()
+            // Every chunk holds exactly one NPI
+            .map(|ChunkFailure { mut npis, error }| (npis.remove(0), error))
+            .collect();
+
+        Ok(PartialResponse {
+            response: bulk.response,
+            failures,
+        })
+    }
+
+    /// Price the same NPIs for each of several billing codes, one request per code
+    ///
+    /// Requests run concurrently, at most [`BulkOptions::concurrency`] at a time. Each
+    /// code is priced once, with the code type given first if it repeats. Every request
+    /// is validated before any is sent, so `npis` must satisfy the usual limits; for
+    /// more NPIs than one request allows, see [`JobPlan`](crate::jobs::JobPlan).
+    ///
+    /// With [`BulkOptions::fail_fast`] on, the first failing code's error is returned.
+    /// With it off, failing codes are reported in [`CodePricing::failures`] and the
+    /// rest in [`CodePricing::responses`]; if every code fails, the first code's
+    /// error is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `codes` is empty, any request is invalid, or a code fails
+    /// as described above.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use docaroo_rs::{DocarooClient, models::CodeType, pricing::BulkOptions};
+    /// # async fn example(npis: Vec<String>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DocarooClient::new("your-api-key");
+    ///
+    /// let codes = [
+    ///     ("99213".to_string(), CodeType::Cpt),
+    ///     ("99214".to_string(), CodeType::Cpt),
+    ///     ("99215".to_string(), CodeType::Cpt),
+    /// ];
+    /// let options = BulkOptions::builder().fail_fast(false).build();
+    ///
+    /// let priced = client.pricing().price_codes(&npis, &codes, options).await?;
+    /// for (code, response) in &priced.responses {
+    ///     println!("{code}: {} providers", response.data.len());
+    /// }
+    /// for (code, error) in &priced.failures {
+    ///     eprintln!("{code}: {error}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn price_codes(
+        &self,
+        npis: &[String],
+        codes: &[(String, CodeType)],
+        options: BulkOptions,
+    ) -> Result<CodePricing> {
+        if codes.is_empty() {
             return Err(DocarooError::InvalidRequest(
-                "Maximum 10 NPIs allowed per request".to_string(),
+                "At least one code must be provided".to_string(),
             ));
         }
 
-        // Validate NPI format (10 digits)
-        for npi in &request.npis {
-            if npi.len() != 10 || !npi.chars().all(|c| c.is_ascii_digit()) {
-                return Err(DocarooError::InvalidRequest(format!(
-                    "Invalid NPI format: '{}'. NPIs must be 10-digit numbers",
-                    npi
-                )));
+        let mut seen = HashSet::new();
+        let requests: Vec<PricingRequest> = codes
+            .iter()
+            .filter(|(code, _)| seen.insert(code.as_str()))
+            .map(|(code, code_type)| {
+                PricingRequest::builder()
+                    .npis(npis.to_vec())
+                    .condition_code(code.clone())
+                    .code_type(*code_type)
+                    .build()
+            })
+            .collect();
+        for request in &requests {
+            self.validate_pricing_request(request)?;
+        }
+
+        let cancellation = options.cancellation.clone();
+        until_cancelled(cancellation.as_ref(), self.price_requests(requests, options)).await
+    }
+
+    /// Send one validated request per code for [`price_codes`](Self::price_codes)
+    async fn price_requests(
+        &self,
+        requests: Vec<PricingRequest>,
+        options: BulkOptions,
+    ) -> Result<CodePricing> {
+        let lookups = futures::stream::iter(requests)
+            .map(|request| async move {
+                let code = request.condition_code.clone();
+                let result = self.get_in_network_rates(request).await;
+                (code, result)
+            })
+            .buffered(options.concurrency.max(1));
+
+        let mut priced = CodePricing {
+            responses: HashMap::new(),
+            failures: HashMap::new(),
+        };
+        if options.fail_fast {
+            priced.responses = lookups
+                .map(|(code, result)| result.map(|response| (code, response)))
+                .try_collect()
+                .await?;
+            return Ok(priced);
+        }
+
+        let mut first_error = None;
+        for (code, result) in lookups.collect::<Vec<_>>().await {
+            match result {
+                Ok(response) => {
+                    priced.responses.insert(code, response);
+                }
+                Err(error) => {
+                    first_error.get_or_insert(code.clone());
+                    priced.failures.insert(code, error);
+                }
+            }
+        }
+        if priced.responses.is_empty() {
+            if let Some(error) = first_error.and_then(|code| priced.failures.remove(&code)) {
+                return Err(error);
             }
         }
 
-        // Validate condition code is not empty
-        if request.condition_code.trim().is_empty() {
-            return Err(DocarooError::InvalidRequest(
-                "Condition code cannot be empty".to_string(),
-            ));
+        Ok(priced)
+    }
+
+    /// Price deduplicated NPIs in chunks of `chunk_size`, merging the results
+    async fn bulk_in_chunks(
+        &self,
+        request: PricingRequest,
+        chunk_size: usize,
+        options: BulkOptions,
+    ) -> Result<BulkPricingResponse> {
+        let mut seen = HashSet::new();
+        let npis: Vec<String> = request
+            .npis
+            .iter()
+            .filter(|npi| seen.insert(npi.as_str()))
+            .cloned()
+            .collect();
+        if npis.is_empty() {
+            self.validate_pricing_request(&request)?;
+        }
+
+        let chunks: Vec<PricingRequest> = npis
+            .chunks(chunk_size)
+            .map(|chunk| request.with_npis(chunk.to_vec()))
+            .collect();
+        for chunk in &chunks {
+            self.validate_pricing_request(chunk)?;
+        }
+
+        let cancellation = options.cancellation.clone();
+        until_cancelled(cancellation.as_ref(), self.merge_chunks(chunks, options)).await
+    }
+
+    /// Send validated chunks for [`bulk_in_chunks`](Self::bulk_in_chunks) and merge
+    /// the responses
+    async fn merge_chunks(
+        &self,
+        chunks: Vec<PricingRequest>,
+        options: BulkOptions,
+    ) -> Result<BulkPricingResponse> {
+        let lookups = futures::stream::iter(chunks)
+            .map(|chunk| async move {
+                let npis = chunk.npis.clone();
+                self.get_in_network_rates(chunk)
+                    .await
+                    .map_err(|error| ChunkFailure { npis, error })
+            })
+            .buffered(options.concurrency.max(1));
+
+        let (responses, failures) = if options.fail_fast {
+            let responses = lookups
+                .map_err(|failure| failure.error)
+                .try_collect::<Vec<_>>()
+                .await?;
+            (responses, Vec::new())
+        } else {
+            let mut responses = Vec::new();
+            let mut failures = Vec::new();
+            for result in lookups.collect::<Vec<_>>().await {
+                match result {
+                    Ok(response) => responses.push(response),
+                    Err(failure) => failures.push(failure),
+                }
+            }
+            if responses.is_empty() {
+                // Every chunk failed, and there is always at least one chunk
+                return Err(failures.remove(0).error);
+            }
+            (responses, failures)
+        };
+
+        let request_ids = responses
+            .iter()
+            .map(|response| response.meta.request_id.clone())
+            .collect();
+        let processing_time_ms = responses
+            .iter()
+            .map(|response| response.meta.processing_time_ms)
+            .fold(0u32, u32::saturating_add);
+        let in_network_records_count = responses
+            .iter()
+            .map(|response| response.meta.in_network_records_count)
+            .fold(0u32, u32::saturating_add);
+
+        let mut response = PricingResponse::merge_all(responses, ConflictStrategy::KeepFirst);
+        response.meta.processing_time_ms = processing_time_ms;
+        response.meta.in_network_records_count = in_network_records_count;
+
+        Ok(BulkPricingResponse {
+            response,
+            request_ids,
+            failures,
+        })
+    }
+
+    /// Find the plan with the lowest average rate for a provider
+    ///
+    /// Issues one pricing request per plan concurrently and compares the cheapest
+    /// `avg_rate` each plan returns for the NPI. Rates with a NaN `avg_rate` are
+    /// ignored.
+    ///
+    /// # Returns
+    ///
+    /// The winning plan ID with its rate, or `None` if no plan returned rates for the NPI
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error encountered by any of the per-plan requests
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use docaroo_rs::{DocarooClient, models::CodeType};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DocarooClient::new("your-api-key");
+    ///
+    /// let plans = vec!["942404110".to_string(), "131624096".to_string()];
+    /// if let Some((plan_id, rate)) = client
+    ///     .pricing()
+    ///     .best_plan("1043566623", "99214", Some(CodeType::Cpt), &plans)
+    ///     .await?
+    /// {
+    ///     println!("Cheapest plan {}: ${:.2}", plan_id, rate.avg_rate);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn best_plan(
+        &self,
+        npi: impl Into<String>,
+        condition_code: impl Into<String>,
+        code_type: Option<CodeType>,
+        plans: &[String],
+    ) -> Result<Option<(String, RateData)>> {
+        let npi = npi.into();
+        let condition_code = condition_code.into();
+
+        let lookups = plans.iter().map(|plan_id| {
+            let request = PricingRequest::builder()
+                .npis(vec![npi.clone()])
+                .condition_code(condition_code.clone())
+                .plan_id(plan_id.clone())
+                .maybe_code_type(code_type)
+                .build();
+
+            async move {
+                let response = self.get_in_network_rates(request).await?;
+                Ok::<_, crate::error::DocarooError>((plan_id.clone(), response))
+            }
+        });
+
+        let responses = futures::future::try_join_all(lookups).await?;
+
+        Ok(responses
+            .into_iter()
+            .filter_map(|(plan_id, response)| {
+                response
+                    .data
+                    .get(&npi)?
+                    .iter()
+                    .filter(|rate| !rate.avg_rate.is_nan())
+                    .min_by(|a, b| a.avg_rate.total_cmp(&b.avg_rate))
+                    .map(|rate| (plan_id, rate.clone()))
+            })
+            .min_by(|a, b| a.1.avg_rate.total_cmp(&b.1.avg_rate)))
+    }
+
+    /// Estimate how many API requests a lookup over `npis` will take
+    ///
+    /// Duplicate NPIs are counted once, and the remainder are split into chunks of at
+    /// most [`MAX_NPIS_PER_PRICING_REQUEST`], so this returns
+    /// `ceil(unique_npis / MAX_NPIS_PER_PRICING_REQUEST)`. Useful for pre-flight cost
+    /// and rate-limit budgeting; no requests are sent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use docaroo_rs::DocarooClient;
+    /// let client = DocarooClient::new("your-api-key");
+    ///
+    /// let npis: Vec<String> = (0..25).map(|i| format!("10000000{:02}", i)).collect();
+    /// assert_eq!(client.pricing().plan_requests(&npis), 3);
+    /// ```
+    pub fn plan_requests(&self, npis: &[String]) -> usize {
+        let unique: HashSet<&str> = npis.iter().map(String::as_str).collect();
+        unique.len().div_ceil(MAX_NPIS_PER_PRICING_REQUEST)
+    }
+
+    /// Estimate how many API requests pricing every NPI for every code will take
+    ///
+    /// Each unique code needs its own chunked lookup, so this is
+    /// [`plan_requests`](Self::plan_requests) multiplied by the number of unique codes.
+    pub fn plan_sweep_requests(&self, npis: &[String], codes: &[String]) -> usize {
+        let unique_codes: HashSet<&str> = codes.iter().map(String::as_str).collect();
+        self.plan_requests(npis) * unique_codes.len()
+    }
+
+    /// Validate a pricing request before sending
+    pub(crate) fn validate_pricing_request(&self, request: &PricingRequest) -> Result<()> {
+        request.validate()?;
+
+        if let Some(code_type) = request.code_type {
+            if self.client.config().strict_code_validation {
+                crate::validation::validate_code_format(&request.condition_code, code_type)?;
+            }
         }
 
         Ok(())
     }
 }
 
+/// Run `future`, giving up with [`DocarooError::Cancelled`] once `cancellation` fires
+async fn until_cancelled<T>(
+    cancellation: Option<&CancellationToken>,
+    future: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    let Some(token) = cancellation else {
+        return future.await;
+    };
+
+    if token.is_cancelled() {
+        return Err(DocarooError::Cancelled);
+    }
+
+    // Dropping the future aborts whatever requests it still has in flight
+    tokio::select! {
+        result = future => result,
+        () = token.cancelled() => Err(DocarooError::Cancelled),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{DocarooError, client::DocarooConfig, models::Payer};
+    use std::sync::Arc;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{body_partial_json, method, path},
+    };
+
+    /// Well-formed NPIs with valid check digits
+    const VALID_NPIS: [&str; 12] = [
+        "1043566623",
+        "1972767655",
+        "1487648176",
+        "1234567893",
+        "1111111112",
+        "1000000004",
+        "1987654328",
+        "1555555550",
+        "1333333338",
+        "1444444446",
+        "1666666668",
+        "1777777776",
+    ];
+
+    fn pricing_body() -> serde_json::Value {
+        serde_json::json!({
+            "data": {
+                "1043566623": [{
+                    "code": "99214",
+                    "codeType": "CPT",
+                    "negotiatedType": "negotiated",
+                    "minRate": 65.87,
+                    "maxRate": 266.88,
+                    "avgRate": 147.03,
+                    "instances": 6
+                }]
+            },
+            "meta": {
+                "planId": "942404110",
+                "payer": "UNH",
+                "requestId": "req_test123",
+                "timestamp": "2025-06-15T23:15:48.734729Z",
+                "processingTimeMs": 912,
+                "inNetworkRecordsCount": 14
+            }
+        })
+    }
+
+    fn mock_client(server: &MockServer) -> DocarooClient {
+        DocarooClient::with_config(
+            DocarooConfig::builder()
+                .api_key("test-key")
+                .base_url(server.uri())
+                .build(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_plan_was_defaulted() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(pricing_body()))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+
+        let request = PricingRequest::builder()
+            .npis(vec!["1043566623".to_string()])
+            .condition_code("99214")
+            .build();
+        let response = client.pricing().get_in_network_rates(request).await.unwrap();
+        assert!(response.plan_was_defaulted());
+        assert_eq!(response.meta.plan_id, "942404110");
+
+        let request = PricingRequest::builder()
+            .npis(vec!["1043566623".to_string()])
+            .condition_code("99214")
+            .plan_id("942404110")
+            .build();
+        let response = client.pricing().get_in_network_rates(request).await.unwrap();
+        assert!(!response.plan_was_defaulted());
+    }
+
+    #[tokio::test]
+    async fn test_default_code_type() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(pricing_body()))
+            .mount(&server)
+            .await;
+
+        let unfilled = mock_client(&server);
+        let filled = DocarooClient::with_config(
+            DocarooConfig::builder()
+                .api_key("test-key")
+                .base_url(server.uri())
+                .default_code_type(CodeType::Cpt)
+                .build(),
+        );
+        let request = PricingRequest::builder()
+            .npis(vec!["1043566623".to_string()])
+            .condition_code("99214")
+            .build();
+
+        unfilled.pricing().get_in_network_rates(request.clone()).await.unwrap();
+        filled.pricing().get_in_network_rates(request.clone()).await.unwrap();
+        // An explicit code type is sent as given
+        filled
+            .pricing()
+            .get_in_network_rates(request.with_code_type(CodeType::Hcpcs))
+            .await
+            .unwrap();
+
+        let bodies: Vec<serde_json::Value> = server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|request| request.body_json().unwrap())
+            .collect();
+        assert_eq!(
+            bodies[0],
+            serde_json::json!({ "npis": ["1043566623"], "conditionCode": "99214" })
+        );
+        assert_eq!(
+            bodies[1],
+            serde_json::json!({
+                "npis": ["1043566623"],
+                "conditionCode": "99214",
+                "codeType": "CPT"
+            })
+        );
+        assert_eq!(bodies[2]["codeType"], "HCPCS");
+    }
+
+    /// Cache recording every TTL it is given
+    #[derive(Debug, Default)]
+    struct MapCache {
+        entries: std::sync::Mutex<HashMap<String, PricingResponse>>,
+        ttls: std::sync::Mutex<Vec<Duration>>,
+    }
+
+    impl crate::cache::Cache for MapCache {
+        fn get<'a>(
+            &'a self,
+            key: &'a str,
+        ) -> crate::transport::BoxFuture<'a, Option<PricingResponse>> {
+            let response = self.entries.lock().unwrap().get(key).cloned();
+            Box::pin(async move { response })
+        }
+
+        fn insert(
+            &self,
+            key: String,
+            response: PricingResponse,
+            ttl: Duration,
+        ) -> crate::transport::BoxFuture<'_, ()> {
+            self.entries.lock().unwrap().insert(key, response);
+            self.ttls.lock().unwrap().push(ttl);
+            Box::pin(async {})
+        }
+    }
+
+    #[tokio::test]
+    async fn test_response_cache() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(pricing_body()))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let cache = Arc::new(MapCache::default());
+        let client = DocarooClient::with_config(
+            DocarooConfig::builder()
+                .api_key("test-key")
+                .base_url(server.uri())
+                .response_cache(cache.clone())
+                .cache_ttl(Duration::from_secs(5))
+                .build(),
+        );
+        let request = PricingRequest::builder()
+            .npis(vec!["1043566623".to_string(), "1972767655".to_string()])
+            .condition_code("99214")
+            .plan_id("942404110")
+            .build();
+
+        let first = client
+            .pricing()
+            .get_in_network_rates(request.clone())
+            .await
+            .unwrap();
+        // Same request with the NPIs in another order is answered from the cache
+        let reordered = request.with_npis(vec![
+            "1972767655".to_string(),
+            "1043566623".to_string(),
+        ]);
+        let second = client.pricing().get_in_network_rates(reordered).await.unwrap();
+        assert_eq!(second.meta.request_id, first.meta.request_id);
+        assert_eq!(second.requested_plan_id.as_deref(), Some("942404110"));
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+        // A different code misses
+        client
+            .pricing()
+            .get_in_network_rates(request.with_condition_code("99213"))
+            .await
+            .unwrap();
+        assert_eq!(cache.entries.lock().unwrap().len(), 2);
+        assert_eq!(*cache.ttls.lock().unwrap(), [Duration::from_secs(5); 2]);
+    }
+
+    #[tokio::test]
+    async fn test_response_cache_with_batch_endpoint() {
+        let server = MockServer::start().await;
+        let mut second = pricing_body();
+        second["meta"]["requestId"] = serde_json::json!("req_second");
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network/batch"))
+            .and(body_partial_json(serde_json::json!({
+                "queries": [{ "conditionCode": "99213" }]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "responses": [second]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(pricing_body()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let cache = Arc::new(MapCache::default());
+        let client = DocarooClient::with_config(
+            DocarooConfig::builder()
+                .api_key("test-key")
+                .base_url(server.uri())
+                .response_cache(cache.clone())
+                .cache_ttl(Duration::from_secs(5))
+                .build(),
+        );
+        let request = PricingRequest::builder()
+            .npis(vec!["1043566623".to_string()])
+            .condition_code("99214")
+            .plan_id("942404110")
+            .build();
+        client
+            .pricing()
+            .get_in_network_rates(request.clone())
+            .await
+            .unwrap();
+
+        // The cached query is answered locally; only the other one is batched
+        let queries = vec![request.clone(), request.with_condition_code("99213")];
+        let responses = client.batch(queries.clone()).await.unwrap();
+        assert_eq!(responses[0].meta.request_id, "req_test123");
+        assert_eq!(responses[1].meta.request_id, "req_second");
+        assert_eq!(responses[1].requested_plan_id.as_deref(), Some("942404110"));
+        assert_eq!(cache.entries.lock().unwrap().len(), 2);
+        assert_eq!(*cache.ttls.lock().unwrap(), [Duration::from_secs(5); 2]);
+
+        // Now both are cached, so nothing is sent
+        let responses = client.batch(queries).await.unwrap();
+        assert_eq!(responses[1].meta.request_id, "req_second");
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_in_network_rates_opt() {
+        let server = MockServer::start().await;
+        let mut empty = pricing_body();
+        empty["data"] = serde_json::json!({});
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .and(body_partial_json(serde_json::json!({ "conditionCode": "00000" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(empty))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .and(body_partial_json(serde_json::json!({ "conditionCode": "99214" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(pricing_body()))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let request = PricingRequest::builder()
+            .npis(vec!["1043566623".to_string()])
+            .condition_code("00000")
+            .build();
+        let response = client.pricing().get_in_network_rates_opt(request.clone()).await;
+        assert!(response.unwrap().is_none());
+
+        let request = request.with_condition_code("99214");
+        let response = client.pricing().get_in_network_rates_opt(request).await.unwrap();
+        assert_eq!(response.unwrap().data.len(), 1);
+    }
+
+    /// Respond with `pricing_body`, pricing the requested code at `avg_rate`
+    fn code_body(code: &str, avg_rate: f64) -> serde_json::Value {
+        let mut body = pricing_body();
+        body["data"]["1043566623"][0]["code"] = serde_json::json!(code);
+        body["data"]["1043566623"][0]["avgRate"] = serde_json::json!(avg_rate);
+        body["meta"]["requestId"] = serde_json::json!(format!("req_{code}"));
+        body
+    }
+
+    #[tokio::test]
+    async fn test_price_codes_runs_concurrently() {
+        let server = MockServer::start().await;
+        for (code, avg_rate) in [("99213", 98.5), ("99214", 147.03), ("99215", 210.0)] {
+            Mock::given(method("POST"))
+                .and(path("/pricing/in-network"))
+                .and(body_partial_json(serde_json::json!({
+                    "conditionCode": code,
+                    "codeType": "CPT"
+                })))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(code_body(code, avg_rate))
+                        .set_delay(Duration::from_millis(300)),
+                )
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let client = mock_client(&server);
+        let npis = vec!["1043566623".to_string(), "1972767655".to_string()];
+        let codes = [
+            ("99213".to_string(), CodeType::Cpt),
+            ("99214".to_string(), CodeType::Cpt),
+            ("99215".to_string(), CodeType::Cpt),
+            // Repeated codes are priced once
+            ("99214".to_string(), CodeType::Hcpcs),
+        ];
+        let options = BulkOptions::builder().concurrency(3).build();
+
+        let started = std::time::Instant::now();
+        let priced = client.pricing().price_codes(&npis, &codes, options).await.unwrap();
+        assert!(started.elapsed() < Duration::from_millis(800), "{:?}", started.elapsed());
+
+        assert!(priced.is_complete());
+        assert_eq!(priced.responses.len(), 3);
+        for (code, avg_rate) in [("99213", 98.5), ("99214", 147.03), ("99215", 210.0)] {
+            let response = &priced.responses[code];
+            assert_eq!(response.meta.request_id, format!("req_{code}"));
+            assert_eq!(response.rates_for("1043566623").unwrap()[0].avg_rate, avg_rate);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_price_codes_partial_failures() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .and(body_partial_json(serde_json::json!({ "conditionCode": "99215" })))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "error": "invalid_request",
+                "message": "Unknown code"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(pricing_body()))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let npis = vec!["1043566623".to_string()];
+        let codes = [
+            ("99214".to_string(), CodeType::Cpt),
+            ("99215".to_string(), CodeType::Cpt),
+        ];
+
+        let options = BulkOptions::builder().fail_fast(false).build();
+        let priced = client.pricing().price_codes(&npis, &codes, options).await.unwrap();
+        assert!(!priced.is_complete());
+        assert!(priced.responses.contains_key("99214"));
+        assert!(matches!(
+            priced.failures["99215"],
+            DocarooError::InvalidRequest(_)
+        ));
+
+        let error = client
+            .pricing()
+            .price_codes(&npis, &codes, BulkOptions::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(error, DocarooError::InvalidRequest(_)));
+
+        // Every code failing returns the first code's error
+        let error = client
+            .pricing()
+            .price_codes(&npis, &codes[1..], BulkOptions::builder().fail_fast(false).build())
+            .await
+            .unwrap_err();
+        assert!(matches!(error, DocarooError::InvalidRequest(m) if m == "Unknown code"));
+
+        let error = client.pricing().price_codes(&npis, &[], BulkOptions::default()).await;
+        assert!(matches!(error, Err(DocarooError::InvalidRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_rates_by_npi() {
+        let server = MockServer::start().await;
+        let mut body_99214 = code_body("99214", 147.03);
+        body_99214["data"]["1972767655"] = serde_json::json!([
+            { "code": "99214", "codeType": "CPT", "negotiatedType": "negotiated",
+              "minRate": 90.0, "maxRate": 180.0, "avgRate": 120.0, "instances": 2 },
+            { "code": "99214", "codeType": "CPT", "negotiatedType": "fee schedule",
+              "minRate": 100.0, "maxRate": 200.0, "avgRate": 150.25, "instances": 9 }
+        ]);
+        let mut body_99213 = code_body("99213", 98.5);
+        body_99213["data"]["1972767655"] = serde_json::json!([]);
+        for (code, body) in [("99214", body_99214), ("99213", body_99213)] {
+            Mock::given(method("POST"))
+                .and(path("/pricing/in-network"))
+                .and(body_partial_json(serde_json::json!({ "conditionCode": code })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(body))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let client = mock_client(&server);
+        let npis = vec![
+            "1043566623".to_string(),
+            "1972767655".to_string(),
+            "1487648176".to_string(),
+        ];
+        let codes = [
+            ("99213".to_string(), CodeType::Cpt),
+            ("99214".to_string(), CodeType::Cpt),
+        ];
+        let priced = client
+            .pricing()
+            .price_codes(&npis, &codes, BulkOptions::default())
+            .await
+            .unwrap();
+        let rates = priced.rates_by_npi();
+
+        assert_eq!(rates.len(), 2);
+        assert_eq!(rates["1043566623"]["99213"].avg_rate, 98.5);
+        assert_eq!(rates["1043566623"]["99214"].avg_rate, 147.03);
+        // No rates for 99213, and the best-supported rate for 99214
+        assert_eq!(rates["1972767655"].len(), 1);
+        assert_eq!(rates["1972767655"]["99214"].avg_rate, 150.25);
+        assert!(!rates.contains_key("1487648176"));
+    }
+
+    #[tokio::test]
+    async fn test_best_plan_picks_cheapest() {
+        let server = MockServer::start().await;
+        for (plan_id, avg_rate) in [("plan-a", 150.0), ("plan-b", 95.5), ("plan-c", 120.0)] {
+            let mut body = pricing_body();
+            body["data"]["1043566623"][0]["avgRate"] = serde_json::json!(avg_rate);
+            body["meta"]["planId"] = serde_json::json!(plan_id);
+            Mock::given(method("POST"))
+                .and(path("/pricing/in-network"))
+                .and(body_partial_json(serde_json::json!({ "planId": plan_id })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(body))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let client = mock_client(&server);
+        let plans = vec!["plan-a".to_string(), "plan-b".to_string(), "plan-c".to_string()];
+
+        let (plan_id, rate) = client
+            .pricing()
+            .best_plan("1043566623", "99214", Some(CodeType::Cpt), &plans)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(plan_id, "plan-b");
+        assert_eq!(rate.avg_rate, 95.5);
+    }
+
+    #[tokio::test]
+    async fn test_validate_against_detects_unrequested_npi() {
+        let server = MockServer::start().await;
+        let mut body = pricing_body();
+        body["data"]["1972767655"] = body["data"]["1043566623"].clone();
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let request = PricingRequest::builder()
+            .npis(vec!["1043566623".to_string()])
+            .condition_code("99214")
+            .build();
+
+        let response = client
+            .pricing()
+            .get_in_network_rates(request.clone())
+            .await
+            .unwrap();
+        let message = response.validate_against(&request).unwrap_err().to_string();
+        assert!(message.contains("1972767655"));
+        assert!(message.contains("not requested"));
+
+        let request = request.with_npis(vec!["1043566623".to_string(), "1972767655".to_string()]);
+        assert!(response.validate_against(&request).is_ok());
+
+        let request = request.with_condition_code("99215");
+        let message = response.validate_against(&request).unwrap_err().to_string();
+        assert!(message.contains("'99214' but '99215'"));
+    }
+
+    #[tokio::test]
+    async fn test_plan_payer_validator_rejects_mismatch() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(pricing_body()))
+            .mount(&server)
+            .await;
+
+        let client = DocarooClient::with_config(
+            DocarooConfig::builder()
+                .api_key("test-key")
+                .base_url(server.uri())
+                .plan_payer_validator(Arc::new(|plan_id: &str, payer: &Payer| {
+                    plan_id != "942404110" || *payer == "AETNA"
+                }))
+                .build(),
+        );
+        let request = PricingRequest::builder()
+            .npis(vec!["1043566623".to_string()])
+            .condition_code("99214")
+            .plan_id("942404110")
+            .build();
+
+        let error = client
+            .pricing()
+            .get_in_network_rates(request.clone())
+            .await
+            .unwrap_err();
+        match error {
+            DocarooError::InvalidRequest(message) => {
+                assert!(message.contains("942404110"));
+                assert!(message.contains("UNH"));
+            }
+            other => panic!("Expected InvalidRequest, got {other:?}"),
+        }
+
+        // Without a validator the same response is accepted
+        let response = mock_client(&server)
+            .pricing()
+            .get_in_network_rates(request)
+            .await
+            .unwrap();
+        assert_eq!(response.meta.payer, "UNH");
+    }
+
+    #[tokio::test]
+    async fn test_inconsistent_rate_hook_collects_violations() {
+        let server = MockServer::start().await;
+        let mut body = pricing_body();
+        body["data"]["1972767655"] = body["data"]["1043566623"].clone();
+        body["data"]["1972767655"][0]["avgRate"] = serde_json::json!(12.5);
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(&server)
+            .await;
+
+        let violations = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&violations);
+        let client = DocarooClient::with_config(
+            DocarooConfig::builder()
+                .api_key("test-key")
+                .base_url(server.uri())
+                .warn_on_inconsistent_rates(true)
+                .inconsistent_rate_hook(Arc::new(move |npi: &str, rate: &RateData| {
+                    recorded.lock().unwrap().push((npi.to_string(), rate.avg_rate));
+                }))
+                .build(),
+        );
+        let request = PricingRequest::builder()
+            .npis(vec!["1043566623".to_string(), "1972767655".to_string()])
+            .condition_code("99214")
+            .build();
+
+        // Violations are reported, not rejected
+        let response = client.pricing().get_in_network_rates(request).await.unwrap();
+        assert_eq!(response.data.len(), 2);
+        assert_eq!(*violations.lock().unwrap(), vec![("1972767655".to_string(), 12.5)]);
+    }
+
+    #[tokio::test]
+    async fn test_tags_survive_round_trip() {
+        let server = MockServer::start().await;
+        let mut body = pricing_body();
+        body["data"]["1972767655"] = body["data"]["1043566623"].clone();
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(&server)
+            .await;
+
+        let mut request = PricingRequest::builder()
+            .npis(vec!["1043566623".to_string(), "1972767655".to_string()])
+            .condition_code("99214")
+            .build();
+        request.tag_npi("1043566623", "provider-0042");
+
+        // Tags are client-side only
+        let json = serde_json::to_value(&request).unwrap();
+        assert!(json.get("tags").is_none());
+
+        let response = mock_client(&server)
+            .pricing()
+            .get_in_network_rates(request.clone())
+            .await
+            .unwrap();
+        let tagged = response.with_tags(&request);
+        assert_eq!(tagged.len(), 1);
+
+        let (tag, rates) = &tagged["1043566623"];
+        assert_eq!(tag, "provider-0042");
+        assert_eq!(rates[0].avg_rate, 147.03);
+    }
+
+    #[tokio::test]
+    async fn test_error_on_suspicious() {
+        let server = MockServer::start().await;
+        let mut body = pricing_body();
+        body["data"] = serde_json::json!({});
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(&server)
+            .await;
+
+        let request = PricingRequest::builder()
+            .npis(vec!["1043566623".to_string()])
+            .condition_code("99214")
+            .build();
+
+        // Off by default: the response is returned and can be inspected
+        let response = mock_client(&server)
+            .pricing()
+            .get_in_network_rates(request.clone())
+            .await
+            .unwrap();
+        assert!(response.is_suspicious());
+
+        let client = DocarooClient::with_config(
+            DocarooConfig::builder()
+                .api_key("test-key")
+                .base_url(server.uri())
+                .error_on_suspicious(true)
+                .build(),
+        );
+        match client.pricing().get_in_network_rates(request).await.unwrap_err() {
+            DocarooError::ApiError { status, code, message, request_id } => {
+                assert_eq!(status, 200);
+                assert_eq!(code, "suspicious_response");
+                assert!(message.contains("14 in-network records"));
+                assert_eq!(request_id.as_deref(), Some("req_test123"));
+            }
+            other => panic!("Expected ApiError, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_is_degraded_after_slow_response() {
+        let server = MockServer::start().await;
+        let mut slow_body = pricing_body();
+        slow_body["meta"]["processingTimeMs"] = serde_json::json!(4000);
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(pricing_body()))
+            .up_to_n_times(5)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(slow_body))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let request = PricingRequest::builder()
+            .npis(vec!["1043566623".to_string()])
+            .condition_code("99214")
+            .build();
+
+        for _ in 0..5 {
+            client.pricing().get_in_network_rates(request.clone()).await.unwrap();
+            assert!(!client.is_degraded(3.0));
+        }
+        assert_eq!(client.stats().processing_time_ema_ms, Some(912.0));
+
+        client.pricing().get_in_network_rates(request).await.unwrap();
+        assert!(client.is_degraded(3.0));
+        assert!(!client.is_degraded(5.0));
+        assert_eq!(client.stats().samples, 6);
+    }
+
+    #[test]
+    fn test_plan_requests() {
+        let pricing = DocarooClient::new("test-key").pricing();
+        let npis = |count: usize| -> Vec<String> {
+            (0..count).map(|i| format!("{:010}", 1_000_000_000 + i)).collect()
+        };
+
+        assert_eq!(pricing.plan_requests(&npis(0)), 0);
+        assert_eq!(pricing.plan_requests(&npis(9)), 1);
+        assert_eq!(pricing.plan_requests(&npis(10)), 1);
+        assert_eq!(pricing.plan_requests(&npis(11)), 2);
+        assert_eq!(pricing.plan_requests(&npis(25)), 3);
+
+        // Duplicates do not cost extra requests
+        let mut duplicated = npis(10);
+        duplicated.extend(npis(10));
+        assert_eq!(pricing.plan_requests(&duplicated), 1);
+
+        let codes = vec!["99213".to_string(), "99214".to_string(), "99213".to_string()];
+        assert_eq!(pricing.plan_sweep_requests(&npis(25), &codes), 6);
+    }
 
     #[test]
     fn test_validate_pricing_request_valid() {
@@ -134,13 +1576,30 @@ mod tests {
         let pricing_client = PricingClient::new(client);
 
         let request = PricingRequest::builder()
-            .npis(vec!["1234567890".to_string()])
+            .npis(vec!["1234567893".to_string()])
             .condition_code("99214")
             .build();
 
         assert!(pricing_client.validate_pricing_request(&request).is_ok());
     }
 
+    #[test]
+    fn test_validate_pricing_request_bad_checksum() {
+        let pricing_client = PricingClient::new(DocarooClient::new("test-key"));
+
+        let request = PricingRequest::builder()
+            .npis(vec!["1043566623".to_string(), "1234567890".to_string()])
+            .condition_code("99214")
+            .build();
+
+        let message = pricing_client
+            .validate_pricing_request(&request)
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("checksum"));
+        assert!(message.contains("1234567890"));
+    }
+
     #[test]
     fn test_validate_pricing_request_empty_npis() {
         let client = DocarooClient::new("test-key");
@@ -151,6 +1610,7 @@ mod tests {
             condition_code: "99214".to_string(),
             plan_id: None,
             code_type: None,
+            tags: Default::default(),
         };
 
         let result = pricing_client.validate_pricing_request(&request);
@@ -172,6 +1632,7 @@ mod tests {
             condition_code: "99214".to_string(),
             plan_id: None,
             code_type: None,
+            tags: Default::default(),
         };
 
         let result = pricing_client.validate_pricing_request(&request);
@@ -182,6 +1643,178 @@ mod tests {
             .contains("Maximum 10 NPIs allowed"));
     }
 
+    /// Respond to a pricing request with one rate for each requested NPI
+    fn echo_npis(request: &wiremock::Request) -> ResponseTemplate {
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        let npis = body["npis"].as_array().unwrap();
+        let rate = pricing_body()["data"]["1043566623"].clone();
+        let data: serde_json::Map<String, serde_json::Value> = npis
+            .iter()
+            .map(|npi| (npi.as_str().unwrap().to_string(), rate.clone()))
+            .collect();
+
+        let mut response = pricing_body();
+        response["data"] = serde_json::Value::Object(data);
+        response["meta"]["requestId"] =
+            serde_json::json!(format!("req_{}", npis[0].as_str().unwrap()));
+        response["meta"]["processingTimeMs"] = serde_json::json!(100);
+        response["meta"]["inNetworkRecordsCount"] = serde_json::json!(npis.len());
+        ResponseTemplate::new(200).set_body_json(response)
+    }
+
+    #[tokio::test]
+    async fn test_get_in_network_rates_bulk_merges_chunks() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .respond_with(echo_npis)
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let mut npis: Vec<String> = (0..25)
+            .map(|i| crate::validation::npi_with_check_digit(100_000_000 + i))
+            .collect();
+        npis.push(npis[0].clone()); // duplicates are priced once
+        let request = PricingRequest::builder()
+            .npis(npis.clone())
+            .condition_code("99214")
+            .build();
+
+        let bulk = mock_client(&server)
+            .pricing()
+            .get_in_network_rates_bulk(request, BulkOptions::builder().concurrency(2).build())
+            .await
+            .unwrap();
+
+        assert_eq!(bulk.response.data.len(), 25);
+        assert!(bulk.failures.is_empty());
+        assert_eq!(
+            bulk.request_ids,
+            vec![
+                format!("req_{}", npis[0]),
+                format!("req_{}", npis[10]),
+                format!("req_{}", npis[20]),
+            ]
+        );
+        assert_eq!(bulk.response.meta.request_id, bulk.request_ids[0]);
+        assert_eq!(bulk.response.meta.processing_time_ms, 300);
+        assert_eq!(bulk.response.meta.in_network_records_count, 25);
+    }
+
+    #[tokio::test]
+    async fn test_get_in_network_rates_bulk_collects_failures() {
+        let server = MockServer::start().await;
+        let npis: Vec<String> = VALID_NPIS.iter().map(|npi| npi.to_string()).collect();
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .and(body_partial_json(serde_json::json!({ "npis": &npis[10..] })))
+            .respond_with(ResponseTemplate::new(500).set_body_string("boom"))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .respond_with(echo_npis)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let request = PricingRequest::builder()
+            .npis(npis.clone())
+            .condition_code("99214")
+            .build();
+
+        let options = BulkOptions::builder().fail_fast(false).build();
+        let bulk = client
+            .pricing()
+            .get_in_network_rates_bulk(request.clone(), options)
+            .await
+            .unwrap();
+        assert_eq!(bulk.response.data.len(), 10);
+        assert_eq!(bulk.failures.len(), 1);
+        assert_eq!(bulk.failures[0].npis, &npis[10..]);
+        assert!(matches!(bulk.failures[0].error, DocarooError::ServerError { status: 500, .. }));
+
+        let error = client
+            .pricing()
+            .get_in_network_rates_bulk(request, BulkOptions::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(error, DocarooError::ServerError { status: 500, .. }));
+    }
+
+    #[tokio::test]
+    async fn test_bulk_and_partial_lookups_stop_on_cancellation() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(pricing_body())
+                    .set_delay(Duration::from_secs(30)),
+            )
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let npis: Vec<String> = VALID_NPIS.iter().map(|npi| npi.to_string()).collect();
+        let request = PricingRequest::builder()
+            .npis(npis)
+            .condition_code("99214")
+            .build();
+
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            canceller.cancel();
+        });
+
+        let started = std::time::Instant::now();
+        let options = BulkOptions::builder()
+            .concurrency(1)
+            .cancellation(token.clone())
+            .build();
+        let error = client
+            .pricing()
+            .get_in_network_rates_bulk(request.clone(), options)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, DocarooError::Cancelled));
+        assert!(started.elapsed() < Duration::from_secs(5));
+        // Only the first of the two chunks was issued
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+        // An already-cancelled token sends nothing
+        let error = client
+            .pricing()
+            .get_in_network_rates_partial_with_cancellation(request, Some(token))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, DocarooError::Cancelled));
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_validate_pricing_request_uses_max_constant() {
+        let client = DocarooClient::new("test-key");
+        let pricing_client = PricingClient::new(client);
+
+        let npis: Vec<String> = VALID_NPIS.iter().map(|npi| npi.to_string()).collect();
+        let request = PricingRequest::builder()
+            .npis(npis[..MAX_NPIS_PER_PRICING_REQUEST].to_vec())
+            .condition_code("99214")
+            .build();
+        assert!(pricing_client.validate_pricing_request(&request).is_ok());
+
+        let request = request.with_npis(npis[..=MAX_NPIS_PER_PRICING_REQUEST].to_vec());
+        let message = pricing_client
+            .validate_pricing_request(&request)
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains(&MAX_NPIS_PER_PRICING_REQUEST.to_string()));
+    }
+
     #[test]
     fn test_validate_pricing_request_invalid_npi_format() {
         let client = DocarooClient::new("test-key");
@@ -196,4 +1829,293 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Invalid NPI format"));
     }
+
+    #[tokio::test]
+    async fn test_per_call_timeout_overrides_config() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(pricing_body())
+                    .set_delay(Duration::from_millis(300)),
+            )
+            .mount(&server)
+            .await;
+
+        let client = DocarooClient::with_config(
+            DocarooConfig::builder()
+                .api_key("test-key")
+                .base_url(server.uri())
+                .timeout(Duration::from_millis(100))
+                .build(),
+        );
+        let request = PricingRequest::builder()
+            .npis(vec![VALID_NPIS[0].to_string()])
+            .condition_code("99214")
+            .build();
+
+        let error = client
+            .pricing()
+            .get_in_network_rates_with_timeout(request.clone(), Duration::from_millis(50))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, DocarooError::Timeout));
+
+        // A longer override wins over the shorter client-wide timeout
+        let response = client
+            .pricing()
+            .get_in_network_rates_with_timeout(request, Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(response.meta.plan_id, "942404110");
+    }
+
+    #[tokio::test]
+    async fn test_lenient_parsing_keeps_good_npis() {
+        let mut body = pricing_body();
+        body["data"]["1972767655"] = serde_json::json!([{
+            "code": "99214",
+            "codeType": "CPT",
+            "negotiatedType": "negotiated",
+            "minRate": "not a number",
+            "maxRate": 266.88,
+            "avgRate": 147.03,
+            "instances": 6
+        }]);
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(&server)
+            .await;
+        let request = PricingRequest::builder()
+            .npis(vec![VALID_NPIS[0].to_string(), VALID_NPIS[1].to_string()])
+            .condition_code("99214")
+            .build();
+
+        // Strict parsing fails the whole response
+        let error = mock_client(&server)
+            .pricing()
+            .get_in_network_rates(request.clone())
+            .await
+            .unwrap_err();
+        assert!(matches!(error, DocarooError::ParseError { .. }));
+
+        let client = DocarooClient::with_config(
+            DocarooConfig::builder()
+                .api_key("test-key")
+                .base_url(server.uri())
+                .lenient_parsing(true)
+                .build(),
+        );
+        let response = client.pricing().get_in_network_rates(request).await.unwrap();
+        assert_eq!(response.data.len(), 1);
+        assert_eq!(response.data["1043566623"][0].avg_rate, 147.03);
+        assert_eq!(response.parse_errors.len(), 1);
+        assert!(response.parse_errors["1972767655"].contains("invalid type"));
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    async fn test_pricing_span_records_fields() {
+        use std::sync::Mutex;
+        use tracing::{
+            field::{Field, Visit},
+            span::{Attributes, Id, Record},
+        };
+        use tracing_subscriber::{Layer, layer::Context, prelude::*};
+
+        type Fields = Arc<Mutex<Vec<(String, String)>>>;
+
+        /// Collects every field recorded on any span
+        struct CaptureLayer(Fields);
+
+        struct CaptureVisitor<'a>(&'a Fields);
+
+        impl Visit for CaptureVisitor<'_> {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                let value = format!("{value:?}").trim_matches('"').to_string();
+                self.0.lock().unwrap().push((field.name().to_string(), value));
+            }
+        }
+
+        impl<S: tracing::Subscriber> Layer<S> for CaptureLayer {
+            fn on_new_span(&self, attrs: &Attributes<'_>, _: &Id, _: Context<'_, S>) {
+                let name = attrs.metadata().name().to_string();
+                self.0.lock().unwrap().push(("span".to_string(), name));
+                attrs.record(&mut CaptureVisitor(&self.0));
+            }
+
+            fn on_record(&self, _: &Id, values: &Record<'_>, _: Context<'_, S>) {
+                values.record(&mut CaptureVisitor(&self.0));
+            }
+        }
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(pricing_body()))
+            .mount(&server)
+            .await;
+
+        let fields: Fields = Arc::default();
+        let _guard = tracing_subscriber::registry()
+            .with(CaptureLayer(fields.clone()))
+            .set_default();
+
+        let request = PricingRequest::builder()
+            .npis(vec![VALID_NPIS[0].to_string(), VALID_NPIS[1].to_string()])
+            .condition_code("99214")
+            .code_type(CodeType::Cpt)
+            .build();
+        mock_client(&server)
+            .pricing()
+            .get_in_network_rates(request)
+            .await
+            .unwrap();
+
+        let fields = fields.lock().unwrap().clone();
+        let field = |name: &str| {
+            fields
+                .iter()
+                .find(|(field, _)| field == name)
+                .map(|(_, value)| value.as_str())
+        };
+        assert_eq!(field("span"), Some("docaroo.pricing"));
+        assert_eq!(field("endpoint"), Some("/pricing/in-network"));
+        assert_eq!(field("npi_count"), Some("2"));
+        assert_eq!(field("code_type"), Some("CPT"));
+        assert_eq!(field("status"), Some("200"));
+        assert_eq!(field("request_id"), Some("req_test123"));
+        assert_eq!(field("processing_time_ms"), Some("912"));
+        assert!(fields.iter().all(|(_, value)| !value.contains("test-key")));
+    }
+
+    #[tokio::test]
+    async fn test_strict_code_validation() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(pricing_body()))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let request = PricingRequest::builder()
+            .npis(vec![VALID_NPIS[0].to_string()])
+            .condition_code("J349")
+            .code_type(CodeType::Hcpcs)
+            .build();
+
+        // Off by default, so the malformed code is sent
+        mock_client(&server)
+            .pricing()
+            .get_in_network_rates(request.clone())
+            .await
+            .unwrap();
+
+        let strict = DocarooClient::with_config(
+            DocarooConfig::builder()
+                .api_key("test-key")
+                .base_url(server.uri())
+                .strict_code_validation(true)
+                .build(),
+        );
+        let error = strict.pricing().get_in_network_rates(request).await.unwrap_err();
+        assert!(matches!(error, DocarooError::InvalidRequest(_)));
+        assert!(error.to_string().contains("Invalid HCPCS code 'J349'"));
+    }
+
+    /// Reject any pricing request that includes one of `bad_npis`, like an API that
+    /// fails the whole request for one unknown provider
+    fn reject_npis(
+        bad_npis: &'static [&'static str],
+    ) -> impl Fn(&wiremock::Request) -> ResponseTemplate {
+        move |request| {
+            let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+            let bad = body["npis"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .filter_map(|npi| npi.as_str())
+                .find(|npi| bad_npis.contains(npi));
+            match bad {
+                Some(npi) => ResponseTemplate::new(422).set_body_json(serde_json::json!({
+                    "error": "unknown_provider",
+                    "message": format!("No provider found for NPI {npi}")
+                })),
+                None => echo_npis(request),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_partial_response_isolates_failing_npis() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .respond_with(reject_npis(&["1487648176"]))
+            .expect(4) // the whole request, then one per NPI
+            .mount(&server)
+            .await;
+
+        let request = PricingRequest::builder()
+            .npis(VALID_NPIS[..3].iter().map(|npi| npi.to_string()).collect::<Vec<_>>())
+            .condition_code("99214")
+            .build();
+        let partial = mock_client(&server)
+            .pricing()
+            .get_in_network_rates_partial(request)
+            .await
+            .unwrap();
+
+        assert!(!partial.is_complete());
+        assert_eq!(partial.response.data.len(), 2);
+        assert!(partial.response.data.contains_key("1043566623"));
+        assert!(partial.response.data.contains_key("1972767655"));
+        assert_eq!(partial.failures.len(), 1);
+        match &partial.failures["1487648176"] {
+            DocarooError::ApiError { code, message, .. } => {
+                assert_eq!(code, "unknown_provider");
+                assert!(message.contains("1487648176"));
+            }
+            other => panic!("Unexpected error: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_partial_response_all_succeed_or_all_fail() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/pricing/in-network"))
+            .respond_with(reject_npis(&["1111111112", "1000000004"]))
+            .mount(&server)
+            .await;
+        let client = mock_client(&server);
+
+        // Everything succeeds in the first request
+        let request = PricingRequest::builder()
+            .npis(vec![VALID_NPIS[0].to_string(), VALID_NPIS[1].to_string()])
+            .condition_code("99214")
+            .build();
+        let partial = client
+            .pricing()
+            .get_in_network_rates_partial(request.clone())
+            .await
+            .unwrap();
+        assert!(partial.is_complete());
+        assert_eq!(partial.response.data.len(), 2);
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+        // Every NPI fails on its own
+        let request = request.with_npis(vec!["1111111112".to_string(), "1000000004".to_string()]);
+        let error = client
+            .pricing()
+            .get_in_network_rates_partial(request)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("1111111112"));
+        assert_eq!(server.received_requests().await.unwrap().len(), 4);
+    }
 }
\ No newline at end of file
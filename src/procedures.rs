@@ -1,10 +1,35 @@
 //! Procedures API operations for likelihood scoring
 
 use crate::{
-    client::DocarooClient,
-    error::Result,
-    models::{LikelihoodRequest, LikelihoodResponse},
+    client::{DocarooClient, HttpMethod},
+    error::{DocarooError, Result},
+    models::{CodeType, IntoCodeType, LikelihoodData, LikelihoodRequest, LikelihoodResponse},
 };
+use futures::{Stream, StreamExt};
+use std::time::Duration;
+
+/// Maximum number of NPIs the API accepts in a single likelihood request
+///
+/// The likelihood endpoint enforces the same limit as pricing and answers 400 when it
+/// is exceeded. The client-side cap can be lowered (or raised, should the server limit
+/// change) with [`DocarooConfig::max_likelihood_npis`](crate::client::DocarooConfig).
+pub const MAX_NPIS_PER_LIKELIHOOD_REQUEST: usize = 10;
+
+/// Named likelihood thresholds shared by the scoring helpers
+///
+/// Each constant is the inclusive lower bound of the matching
+/// [`ConfidenceBand`](crate::models::ConfidenceBand); scores below
+/// [`UNLIKELY`](thresholds::UNLIKELY) are very unlikely.
+pub mod thresholds {
+    /// Scores at or above this value are highly likely
+    pub const HIGHLY_LIKELY: f64 = 0.8;
+    /// Scores at or above this value are likely
+    pub const LIKELY: f64 = 0.6;
+    /// Scores at or above this value are moderately likely
+    pub const MODERATE: f64 = 0.4;
+    /// Scores at or above this value are unlikely; anything lower is very unlikely
+    pub const UNLIKELY: f64 = 0.2;
+}
 
 /// Client for procedure likelihood operations
 #[derive(Debug, Clone)]
@@ -43,7 +68,7 @@ impl ProceduresClient {
     /// # Example
     ///
     /// ```no_run
-    /// use docaroo_rs::{DocarooClient, models::LikelihoodRequest};
+    /// use docaroo_rs::{DocarooClient, models::{CodeType, LikelihoodRequest}};
     ///
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = DocarooClient::new("your-api-key");
@@ -51,7 +76,7 @@ impl ProceduresClient {
     /// let request = LikelihoodRequest::builder()
     ///     .npis(vec!["1487648176".to_string()])
     ///     .condition_code("99214")
-    ///     .code_type("CPT")
+    ///     .code_type(CodeType::Cpt)
     ///     .build();
     ///
     /// let response = client.procedures().get_likelihood(request).await?;
@@ -64,58 +89,69 @@ impl ProceduresClient {
     /// # }
     /// ```
     pub async fn get_likelihood(&self, request: LikelihoodRequest) -> Result<LikelihoodResponse> {
+        self.fetch_likelihood(request, None).await
+    }
+
+    /// Get procedure likelihood scores with a timeout for this call only
+    ///
+    /// Like [`get_likelihood`](Self::get_likelihood), but `timeout` replaces
+    /// [`DocarooConfig::timeout`](crate::client::DocarooConfig) for each attempt.
+    /// Exceeding it returns [`DocarooError::Timeout`].
+    pub async fn get_likelihood_with_timeout(
+        &self,
+        request: LikelihoodRequest,
+        timeout: Duration,
+    ) -> Result<LikelihoodResponse> {
+        self.fetch_likelihood(request, Some(timeout)).await
+    }
+
+    /// Validate and send a likelihood request
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "docaroo.likelihood",
+            skip_all,
+            fields(
+                endpoint = "/procedures/likelihood",
+                npi_count = request.npis.len(),
+                code_type = request.code_type.as_str(),
+                status = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+                processing_time_ms = tracing::field::Empty,
+            ),
+        )
+    )]
+    async fn fetch_likelihood(
+        &self,
+        request: LikelihoodRequest,
+        timeout: Option<Duration>,
+    ) -> Result<LikelihoodResponse> {
         // Validate request
         self.validate_likelihood_request(&request)?;
+        self.client.check_code_type_supported(Some(request.code_type)).await?;
 
-        // Build URL
-        let url = self.client.build_url("/procedures/likelihood")?;
-
-        // Send request
-        let response = self
+        // Send request, retrying according to the configured policy
+        let response: LikelihoodResponse = self
             .client
-            .http_client()
-            .post(url)
-            .json(&request)
-            .send()
+            .send_json(HttpMethod::Post, "/procedures/likelihood", &request, timeout)
             .await?;
+        self.client.record_processing_time(response.meta.processing_time_ms);
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::Span::current();
+            span.record("request_id", response.meta.request_id.as_str());
+            span.record("processing_time_ms", response.meta.processing_time_ms);
+        }
 
-        // Handle response
-        DocarooClient::handle_response(response).await
+        Ok(response)
     }
 
     /// Validate a likelihood request before sending
     fn validate_likelihood_request(&self, request: &LikelihoodRequest) -> Result<()> {
-        use crate::error::DocarooError;
+        request.validate_with_max_npis(self.client.config().max_likelihood_npis)?;
 
-        // Validate NPIs
-        if request.npis.is_empty() {
-            return Err(DocarooError::InvalidRequest(
-                "At least one NPI must be provided".to_string(),
-            ));
-        }
-
-        // Validate NPI format (10 digits)
-        for npi in &request.npis {
-            if npi.len() != 10 || !npi.chars().all(|c| c.is_ascii_digit()) {
-                return Err(DocarooError::InvalidRequest(format!(
-                    "Invalid NPI format: '{}'. NPIs must be 10-digit numbers",
-                    npi
-                )));
-            }
-        }
-
-        // Validate condition code is not empty
-        if request.condition_code.trim().is_empty() {
-            return Err(DocarooError::InvalidRequest(
-                "Condition code cannot be empty".to_string(),
-            ));
-        }
-
-        // Validate code type is not empty
-        if request.code_type.trim().is_empty() {
-            return Err(DocarooError::InvalidRequest(
-                "Code type cannot be empty".to_string(),
-            ));
+        if self.client.config().strict_code_validation {
+            crate::validation::validate_code_format(&request.condition_code, request.code_type)?;
         }
 
         Ok(())
@@ -130,18 +166,23 @@ impl ProceduresClient {
     ///
     /// * `npis` - List of National Provider Identifiers
     /// * `condition_code` - Medical billing code
-    /// * `code_type` - Medical billing code standard (e.g., "CPT")
+    /// * `code_type` - Medical billing code standard, e.g. `CodeType::Cpt` or `"CPT"`
+    ///
+    /// # Errors
+    ///
+    /// Returns `DocarooError::InvalidRequest` for an unknown code type name, in addition
+    /// to the errors from [`get_likelihood`](Self::get_likelihood).
     ///
     /// # Example
     ///
     /// ```no_run
-    /// # use docaroo_rs::DocarooClient;
+    /// # use docaroo_rs::{DocarooClient, models::CodeType};
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = DocarooClient::new("your-api-key");
     /// 
-    /// let npis = vec!["1487648176", "1234567890"];
+    /// let npis = vec!["1487648176", "1234567893"];
     /// let response = client.procedures()
-    ///     .check_providers(&npis, "99214", "CPT")
+    ///     .check_providers(&npis, "99214", CodeType::Cpt)
     ///     .await?;
     /// # Ok(())
     /// # }
@@ -150,16 +191,157 @@ impl ProceduresClient {
         &self,
         npis: &[&str],
         condition_code: impl Into<String>,
-        code_type: impl Into<String>,
+        code_type: impl IntoCodeType,
     ) -> Result<LikelihoodResponse> {
         let request = LikelihoodRequest::builder()
             .npis(npis.iter().map(|&s| s.to_string()).collect::<Vec<_>>())
             .condition_code(condition_code)
-            .code_type(code_type)
+            .code_type(code_type.try_into_code_type()?)
             .build();
 
         self.get_likelihood(request).await
     }
+
+    /// Score one provider for the same code under several code systems
+    ///
+    /// Issues one likelihood request per code type concurrently, which is useful for
+    /// finding out which code system a bare code string belongs to.
+    ///
+    /// # Returns
+    ///
+    /// `(code type, likelihood)` pairs sorted by likelihood, highest first, with NaN
+    /// scores last. Code types whose request fails, or whose response has no score for
+    /// the NPI, are left out and logged as a warning.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if no code types are given; per-type failures are dropped.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use docaroo_rs::{DocarooClient, models::CodeType};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DocarooClient::new("your-api-key");
+    ///
+    /// let scores = client
+    ///     .procedures()
+    ///     .likelihood_across_types("1487648176", "0470", &[CodeType::Rc, CodeType::MsDrg])
+    ///     .await?;
+    /// for (code_type, likelihood) in scores {
+    ///     println!("{}: {:.2}", code_type.as_str(), likelihood);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn likelihood_across_types(
+        &self,
+        npi: impl Into<String>,
+        code: impl Into<String>,
+        types: &[CodeType],
+    ) -> Result<Vec<(CodeType, f64)>> {
+        if types.is_empty() {
+            return Err(DocarooError::InvalidRequest(
+                "At least one code type must be provided".to_string(),
+            ));
+        }
+
+        let npi = npi.into();
+        let code = code.into();
+
+        let lookups = types.iter().map(|&code_type| {
+            let request = LikelihoodRequest::builder()
+                .npis(vec![npi.clone()])
+                .condition_code(code.clone())
+                .code_type(code_type)
+                .build();
+            async move { (code_type, self.get_likelihood(request).await) }
+        });
+
+        let mut scores = Vec::with_capacity(types.len());
+        for (code_type, result) in futures::future::join_all(lookups).await {
+            match result {
+                Ok(response) => match response.data.get(&npi) {
+                    Some(data) => scores.push((code_type, data.likelihood)),
+                    None => log::warn!(
+                        "No {} likelihood returned for NPI {}",
+                        code_type.as_str(),
+                        npi
+                    ),
+                },
+                Err(e) => log::warn!(
+                    "{} likelihood request for NPI {} failed: {}",
+                    code_type.as_str(),
+                    npi,
+                    e
+                ),
+            }
+        }
+
+        scores.sort_by(|a, b| crate::models::cmp_desc_nan_last(a.1, b.1));
+        Ok(scores)
+    }
+
+    /// Score one provider for many procedure codes, yielding each score as it arrives
+    ///
+    /// Sends one likelihood request per code with at most `concurrency` in flight.
+    /// Items are yielded in completion order, not input order, as `(code, data)`
+    /// pairs. A failed request yields an `Err` item without ending the stream, as does
+    /// a response with no score for the NPI ([`DocarooError::ParseError`]).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use docaroo_rs::{DocarooClient, models::CodeType};
+    /// use futures::StreamExt;
+    ///
+    /// # async fn example(codes: Vec<(String, CodeType)>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DocarooClient::new("your-api-key");
+    /// let procedures = client.procedures();
+    ///
+    /// let mut scores = procedures.likelihood_stream("1487648176", codes, 8);
+    /// while let Some(result) = scores.next().await {
+    ///     match result {
+    ///         Ok((code, data)) => println!("{code}: {:.2}", data.likelihood),
+    ///         Err(e) => eprintln!("lookup failed: {e}"),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn likelihood_stream(
+        &self,
+        npi: &str,
+        codes: Vec<(String, CodeType)>,
+        concurrency: usize,
+    ) -> impl Stream<Item = Result<(String, LikelihoodData)>> + '_ {
+        let npi = npi.to_string();
+
+        futures::stream::iter(codes)
+            .map(move |(code, code_type)| {
+                let npi = npi.clone();
+                async move {
+                    let request = LikelihoodRequest::builder()
+                        .npis(vec![npi.clone()])
+                        .condition_code(code.clone())
+                        .code_type(code_type)
+                        .build();
+                    let response = self.get_likelihood(request).await?;
+                    let data = response.data.into_iter().find(|(key, _)| *key == npi);
+                    match data {
+                        Some((_, data)) => Ok((code, data)),
+                        None => Err(DocarooError::ParseError {
+                            message: format!(
+                                "No {} {} likelihood returned for NPI {}",
+                                code_type, code, npi
+                            ),
+                            body: None,
+                        }),
+                    }
+                }
+            })
+            .buffer_unordered(concurrency.max(1))
+    }
 }
 
 #[cfg(test)]
@@ -172,9 +354,9 @@ mod tests {
         let procedures_client = ProceduresClient::new(client);
 
         let request = LikelihoodRequest::builder()
-            .npis(vec![String::from("1234567890")])
+            .npis(vec![String::from("1234567893")])
             .condition_code("99214")
-            .code_type("CPT")
+            .code_type(CodeType::Cpt)
             .build();
 
         assert!(procedures_client.validate_likelihood_request(&request).is_ok());
@@ -188,7 +370,7 @@ mod tests {
         let request = LikelihoodRequest {
             npis: vec![],
             condition_code: "99214".to_string(),
-            code_type: "CPT".to_string(),
+            code_type: CodeType::Cpt,
         };
 
         let result = procedures_client.validate_likelihood_request(&request);
@@ -207,7 +389,7 @@ mod tests {
         let request = LikelihoodRequest::builder()
             .npis(vec![String::from("ABC1234567")]) // Contains letters
             .condition_code("99214")
-            .code_type("CPT")
+            .code_type(CodeType::Cpt)
             .build();
 
         let result = procedures_client.validate_likelihood_request(&request);
@@ -215,22 +397,188 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("Invalid NPI format"));
     }
 
+    #[tokio::test]
+    async fn test_likelihood_across_types_sorted() {
+        use crate::client::DocarooConfig;
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{body_partial_json, method, path},
+        };
+
+        let server = MockServer::start().await;
+        for (code_type, likelihood) in [("CPT", 0.35), ("HCPCS", 0.92), ("RC", 0.5)] {
+            let response = if code_type == "RC" {
+                ResponseTemplate::new(500).set_body_json(serde_json::json!({
+                    "error": "internal_error",
+                    "message": "Scoring unavailable"
+                }))
+            } else {
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "data": {
+                        "1487648176": {
+                            "code": "99214",
+                            "codeType": code_type,
+                            "likelihood": likelihood
+                        }
+                    },
+                    "meta": {
+                        "requestId": "req_test456",
+                        "timestamp": "2025-06-15T23:22:22Z",
+                        "processingTimeMs": 731,
+                        "outOfNetworkRecordsCount": 68
+                    }
+                }))
+            };
+            Mock::given(method("POST"))
+                .and(path("/procedures/likelihood"))
+                .and(body_partial_json(serde_json::json!({ "codeType": code_type })))
+                .respond_with(response)
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let client = DocarooClient::with_config(
+            DocarooConfig::builder()
+                .api_key("test-key")
+                .base_url(server.uri())
+                .build(),
+        );
+
+        let scores = client
+            .procedures()
+            .likelihood_across_types(
+                "1487648176",
+                "99214",
+                &[CodeType::Cpt, CodeType::Hcpcs, CodeType::Rc],
+            )
+            .await
+            .unwrap();
+        assert_eq!(scores, vec![(CodeType::Hcpcs, 0.92), (CodeType::Cpt, 0.35)]);
+    }
+
     #[test]
-    fn test_validate_likelihood_request_empty_code_type() {
-        let client = DocarooClient::new("test-key");
-        let procedures_client = ProceduresClient::new(client);
+    fn test_validate_likelihood_request_too_many_npis() {
+        use crate::client::DocarooConfig;
 
-        let request = LikelihoodRequest {
-            npis: vec!["1234567890".to_string()],
-            condition_code: "99214".to_string(),
-            code_type: "".to_string(),
-        };
+        let npis: Vec<String> = (0..=MAX_NPIS_PER_LIKELIHOOD_REQUEST)
+            .map(|i| crate::validation::npi_with_check_digit(100_000_000 + i as u32))
+            .collect();
+        let request = LikelihoodRequest::builder()
+            .npis(npis[..MAX_NPIS_PER_LIKELIHOOD_REQUEST].to_vec())
+            .condition_code("99214")
+            .code_type(CodeType::Cpt)
+            .build();
 
+        let procedures_client = ProceduresClient::new(DocarooClient::new("test-key"));
+        assert!(procedures_client.validate_likelihood_request(&request).is_ok());
+
+        let request = request.with_npis(npis);
         let result = procedures_client.validate_likelihood_request(&request);
-        assert!(result.is_err());
         assert!(result
             .unwrap_err()
             .to_string()
-            .contains("Code type cannot be empty"));
+            .contains("Maximum 10 NPIs allowed per request"));
+
+        let client = DocarooClient::with_config(
+            DocarooConfig::builder()
+                .api_key("test-key")
+                .max_likelihood_npis(3)
+                .build(),
+        );
+        let request = request.with_npis(request.npis[..4].to_vec());
+        let result = ProceduresClient::new(client).validate_likelihood_request(&request);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Maximum 3 NPIs allowed per request"));
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_likelihood_stream_yields_every_code() {
+        use futures::StreamExt;
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{body_partial_json, method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/procedures/likelihood"))
+            .respond_with(move |request: &wiremock::Request| {
+                let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+                let code = body["conditionCode"].as_str().unwrap().to_string();
+                // Earlier codes answer more slowly, so completion order is reversed
+                let delay = 200 - 40 * code[4..].parse::<u64>().unwrap();
+                ResponseTemplate::new(200)
+                    .set_delay(Duration::from_millis(delay))
+                    .set_body_json(serde_json::json!({
+                        "data": {
+                            "1487648176": { "code": code, "codeType": "CPT", "likelihood": 0.5 }
+                        },
+                        "meta": {
+                            "requestId": format!("req_{code}"),
+                            "timestamp": "2025-06-15T23:22:22.395111Z",
+                            "processingTimeMs": 10,
+                            "outOfNetworkRecordsCount": 1
+                        }
+                    }))
+            })
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(serde_json::json!({ "conditionCode": "99214" })))
+            .respond_with(ResponseTemplate::new(500))
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(serde_json::json!({ "conditionCode": "99213" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {},
+                "meta": {
+                    "requestId": "req_empty",
+                    "timestamp": "2025-06-15T23:22:22.395111Z",
+                    "processingTimeMs": 10,
+                    "outOfNetworkRecordsCount": 0
+                }
+            })))
+            .with_priority(1)
+            .mount(&server)
+            .await;
+
+        let client = DocarooClient::with_config(
+            crate::client::DocarooConfig::builder()
+                .api_key("test-key")
+                .base_url(server.uri())
+                .build(),
+        );
+        let procedures = client.procedures();
+        let codes: Vec<(String, CodeType)> = (0..5)
+            .map(|i| (format!("9921{i}"), CodeType::Cpt))
+            .collect();
+
+        let results: Vec<_> = procedures
+            .likelihood_stream("1487648176", codes, 5)
+            .collect()
+            .await;
+        assert_eq!(results.len(), 5);
+
+        let scored: Vec<&str> = results
+            .iter()
+            .filter_map(|result| result.as_ref().ok())
+            .map(|(code, _)| code.as_str())
+            .collect();
+        // Yielded as they complete, fastest (last) first
+        assert_eq!(scored, ["99212", "99211", "99210"]);
+
+        let errors: Vec<_> = results.iter().filter_map(|result| result.as_ref().err()).collect();
+        assert_eq!(errors.len(), 2);
+        assert!(errors
+            .iter()
+            .any(|e| matches!(e, DocarooError::ServerError { status: 500, .. })));
+        assert!(errors
+            .iter()
+            .any(|e| matches!(e, DocarooError::ParseError { message, .. } if message.contains("99213"))));
+    }
+}
@@ -0,0 +1,264 @@
+//! Automatic retry with exponential backoff
+
+use crate::error::DocarooError;
+use bon::Builder;
+use std::{fmt, sync::Arc, time::Duration};
+
+/// Details of an upcoming retry, passed to [`RetryPolicy::on_retry`]
+#[derive(Debug, Clone)]
+pub struct RetryAttempt {
+    /// Retry number, starting at 1 for the first retry
+    pub attempt: u32,
+    /// Time the client will wait before sending the retry
+    pub delay: Duration,
+    /// Error that triggered the retry
+    pub error: String,
+    /// API endpoint being retried (e.g. `/pricing/in-network`)
+    pub endpoint: &'static str,
+}
+
+/// Callback invoked before each retry's backoff sleep
+pub type RetryCallback = Arc<dyn Fn(RetryAttempt) + Send + Sync>;
+
+/// Policy controlling automatic retries of retryable errors
+///
+/// Only errors for which [`DocarooError::is_retryable`] is true are retried.
+/// `RateLimitExceeded` waits for the server's `retry_after`; other errors use
+/// exponential backoff starting at `base_delay`. Every delay is capped at `max_delay`.
+///
+/// # Example
+///
+/// ```
+/// use docaroo_rs::{client::DocarooConfig, retry::RetryPolicy};
+/// use std::time::Duration;
+///
+/// let config = DocarooConfig::builder()
+///     .api_key("your-api-key")
+///     .retry_policy(
+///         RetryPolicy::builder()
+///             .max_retries(5)
+///             .base_delay(Duration::from_millis(250))
+///             .build(),
+///     )
+///     .build();
+/// ```
+#[derive(Clone, Builder)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the initial attempt
+    #[builder(default = 3)]
+    pub max_retries: u32,
+
+    /// Delay before the first retry; doubles on each subsequent retry
+    #[builder(default = Duration::from_millis(500))]
+    pub base_delay: Duration,
+
+    /// Upper bound on any single delay, including server-requested ones
+    #[builder(default = Duration::from_secs(30))]
+    pub max_delay: Duration,
+
+    /// Randomize backoff delays to between half and all of their nominal value
+    #[builder(default = true)]
+    pub jitter: bool,
+
+    /// Called before each backoff sleep, e.g. for logging or metrics
+    pub on_retry: Option<RetryCallback>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_retries", &self.max_retries)
+            .field("base_delay", &self.base_delay)
+            .field("max_delay", &self.max_delay)
+            .field("jitter", &self.jitter)
+            .field("on_retry", &self.on_retry.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
+
+impl RetryPolicy {
+    /// Compute the delay before a retry (1-based) triggered by `error`
+    pub(crate) fn delay_for(&self, attempt: u32, error: &DocarooError) -> Duration {
+        let delay = match error {
+            DocarooError::RateLimitExceeded { retry_after } => Duration::from_secs(*retry_after),
+            _ => {
+                let backoff = self
+                    .base_delay
+                    .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)));
+                if self.jitter {
+                    let half = backoff / 2;
+                    half + half.mul_f64(fastrand::f64())
+                } else {
+                    backoff
+                }
+            }
+        };
+
+        delay.min(self.max_delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        DocarooClient,
+        client::DocarooConfig,
+        models::{CodeType, LikelihoodRequest, PricingRequest},
+    };
+    use std::sync::Mutex;
+    use wiremock::{Mock, MockServer, ResponseTemplate, matchers::method};
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        let policy = RetryPolicy::builder()
+            .base_delay(Duration::from_millis(100))
+            .max_delay(Duration::from_millis(350))
+            .jitter(false)
+            .build();
+        let error = DocarooError::Timeout;
+
+        assert_eq!(policy.delay_for(1, &error), Duration::from_millis(100));
+        assert_eq!(policy.delay_for(2, &error), Duration::from_millis(200));
+        assert_eq!(policy.delay_for(3, &error), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn test_jitter_stays_within_bounds() {
+        let policy = RetryPolicy::builder()
+            .base_delay(Duration::from_millis(100))
+            .build();
+
+        for _ in 0..100 {
+            let delay = policy.delay_for(2, &DocarooError::Timeout);
+            assert!(delay >= Duration::from_millis(100));
+            assert!(delay <= Duration::from_millis(200));
+        }
+    }
+
+    #[test]
+    fn test_rate_limit_honors_retry_after() {
+        let policy = RetryPolicy::default();
+        let error = DocarooError::RateLimitExceeded { retry_after: 2 };
+        assert_eq!(policy.delay_for(1, &error), Duration::from_secs(2));
+
+        let error = DocarooError::RateLimitExceeded { retry_after: 600 };
+        assert_eq!(policy.delay_for(1, &error), policy.max_delay);
+    }
+
+    #[tokio::test]
+    async fn test_on_retry_fires_for_each_retry() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(429).set_body_json(serde_json::json!({
+                "error": "rate_limit_exceeded",
+                "message": "Too many requests",
+                "details": { "retryAfter": 1 }
+            })))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {},
+                "meta": {
+                    "planId": "942404110",
+                    "payer": "UNH",
+                    "requestId": "req_retry",
+                    "timestamp": "2025-06-15T23:15:48.734729Z",
+                    "processingTimeMs": 12,
+                    "inNetworkRecordsCount": 0
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let attempts = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&attempts);
+        let policy = RetryPolicy::builder()
+            .max_delay(Duration::from_millis(10))
+            .on_retry(Arc::new(move |attempt: RetryAttempt| {
+                recorded.lock().unwrap().push(attempt);
+            }))
+            .build();
+        let client = DocarooClient::with_config(
+            DocarooConfig::builder()
+                .api_key("test-key")
+                .base_url(server.uri())
+                .retry_policy(policy)
+                .build(),
+        );
+
+        let request = PricingRequest::builder()
+            .npis(vec!["1043566623".to_string()])
+            .condition_code("99214")
+            .build();
+        let response = client.pricing().get_in_network_rates(request).await.unwrap();
+        assert_eq!(response.meta.request_id, "req_retry");
+
+        let attempts = attempts.lock().unwrap();
+        assert_eq!(attempts.len(), 2);
+        assert_eq!(attempts[0].attempt, 1);
+        assert_eq!(attempts[1].attempt, 2);
+        assert_eq!(attempts[0].endpoint, "/pricing/in-network");
+        assert_eq!(attempts[0].delay, Duration::from_millis(10));
+        assert!(attempts[0].error.contains("Rate limit exceeded"));
+    }
+
+    #[tokio::test]
+    async fn test_likelihood_retries_rate_limit_then_succeeds() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(429).set_body_json(serde_json::json!({
+                "error": "rate_limit_exceeded",
+                "message": "Too many requests",
+                "details": { "retryAfter": 1 }
+            })))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "1487648176": { "code": "99214", "codeType": "CPT", "likelihood": 0.9 }
+                },
+                "meta": {
+                    "requestId": "req_likelihood_retry",
+                    "timestamp": "2025-06-15T23:22:22.395111Z",
+                    "processingTimeMs": 31,
+                    "outOfNetworkRecordsCount": 68
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = DocarooClient::with_config(
+            DocarooConfig::builder()
+                .api_key("test-key")
+                .base_url(server.uri())
+                .retry_policy(
+                    RetryPolicy::builder()
+                        .max_delay(Duration::from_millis(10))
+                        .build(),
+                )
+                .build(),
+        );
+
+        let request = LikelihoodRequest::builder()
+            .npis(vec!["1487648176".to_string()])
+            .condition_code("99214")
+            .code_type(CodeType::Cpt)
+            .build();
+        let response = client.procedures().get_likelihood(request).await.unwrap();
+        assert_eq!(response.meta.request_id, "req_likelihood_retry");
+        assert_eq!(response.data["1487648176"].likelihood, 0.9);
+    }
+}
@@ -0,0 +1,155 @@
+//! Client-side statistics about API performance
+
+use chrono::{DateTime, TimeDelta, Utc};
+use reqwest::header::HeaderMap;
+
+/// Rolling statistics about responses received by a [`DocarooClient`](crate::DocarooClient)
+///
+/// Tracks an exponential moving average (EMA) of the server-reported
+/// `processing_time_ms`, so callers can notice when the API slows down relative to
+/// its recent baseline. Obtain a snapshot with
+/// [`DocarooClient::stats`](crate::DocarooClient::stats).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClientStats {
+    /// Number of processing time samples recorded
+    pub samples: u64,
+    /// Most recent processing time in milliseconds
+    pub last_processing_time_ms: Option<u32>,
+    /// EMA of processing time in milliseconds, including the most recent sample
+    pub processing_time_ema_ms: Option<f64>,
+    /// EMA of processing time in milliseconds as it was before the most recent sample
+    pub baseline_ema_ms: Option<f64>,
+}
+
+impl ClientStats {
+    /// Weight given to each new sample in the moving average
+    pub const EMA_ALPHA: f64 = 0.2;
+
+    /// Record the processing time reported by a response
+    pub(crate) fn record(&mut self, processing_time_ms: u32) {
+        let sample = f64::from(processing_time_ms);
+
+        self.baseline_ema_ms = self.processing_time_ema_ms;
+        self.processing_time_ema_ms = Some(match self.processing_time_ema_ms {
+            Some(ema) => Self::EMA_ALPHA * sample + (1.0 - Self::EMA_ALPHA) * ema,
+            None => sample,
+        });
+        self.last_processing_time_ms = Some(processing_time_ms);
+        self.samples += 1;
+    }
+
+    /// Check whether the latest sample exceeds `factor` times the baseline average
+    ///
+    /// The latest sample is compared against the average of the samples before it, so
+    /// a single slow call is not diluted by its own contribution. Returns `false` until
+    /// at least two samples have been recorded.
+    pub fn is_degraded(&self, factor: f64) -> bool {
+        match (self.last_processing_time_ms, self.baseline_ema_ms) {
+            (Some(latest), Some(baseline)) => f64::from(latest) > factor * baseline,
+            _ => false,
+        }
+    }
+}
+
+/// Rate-limit headers reported by the most recent response that carried them
+///
+/// Read from `X-RateLimit-Limit`, `X-RateLimit-Remaining` and `X-RateLimit-Reset` on
+/// every response, successful or not, so callers can throttle before receiving a 429.
+/// Obtain a snapshot with [`DocarooClient::rate_limit`](crate::DocarooClient::rate_limit).
+///
+/// A response without any of these headers leaves the previous snapshot in place.
+/// Header values that are not non-negative integers are treated as absent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    /// Requests allowed in the current window (`X-RateLimit-Limit`)
+    pub limit: Option<u64>,
+    /// Requests left in the current window (`X-RateLimit-Remaining`)
+    pub remaining: Option<u64>,
+    /// `X-RateLimit-Reset` as sent: seconds until the window resets, or a Unix
+    /// timestamp; see [`reset_at`](Self::reset_at)
+    pub reset: Option<u64>,
+    /// When the response carrying these headers was received
+    pub received_at: DateTime<Utc>,
+}
+
+impl RateLimitInfo {
+    /// Values of `reset` at or above this are read as Unix timestamps (2001-09-09)
+    const EPOCH_THRESHOLD: u64 = 1_000_000_000;
+
+    /// Parse the rate-limit headers of a response, if it has any
+    pub(crate) fn from_headers(headers: &HeaderMap, received_at: DateTime<Utc>) -> Option<Self> {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<u64>().ok())
+        };
+
+        let info = Self {
+            limit: header("x-ratelimit-limit"),
+            remaining: header("x-ratelimit-remaining"),
+            reset: header("x-ratelimit-reset"),
+            received_at,
+        };
+        (info.limit.is_some() || info.remaining.is_some() || info.reset.is_some()).then_some(info)
+    }
+
+    /// When the current window resets
+    ///
+    /// `reset` values of at least one billion are Unix timestamps; smaller values are
+    /// seconds counted from `received_at`.
+    pub fn reset_at(&self) -> Option<DateTime<Utc>> {
+        let reset = self.reset?;
+        if reset >= Self::EPOCH_THRESHOLD {
+            DateTime::from_timestamp(i64::try_from(reset).ok()?, 0)
+        } else {
+            self.received_at
+                .checked_add_signed(TimeDelta::seconds(i64::try_from(reset).ok()?))
+        }
+    }
+
+    /// Check whether the window had no requests left when the headers were received
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == Some(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ema_tracks_samples() {
+        let mut stats = ClientStats::default();
+        assert!(!stats.is_degraded(2.0));
+
+        stats.record(100);
+        assert_eq!(stats.processing_time_ema_ms, Some(100.0));
+        assert!(!stats.is_degraded(2.0));
+
+        stats.record(200);
+        assert_eq!(stats.processing_time_ema_ms, Some(120.0));
+        assert_eq!(stats.baseline_ema_ms, Some(100.0));
+        assert_eq!(stats.samples, 2);
+    }
+
+    #[test]
+    fn test_rate_limit_info_from_headers() {
+        let received_at = DateTime::from_timestamp(1_750_000_000, 0).unwrap();
+        let mut headers = HeaderMap::new();
+        assert_eq!(RateLimitInfo::from_headers(&headers, received_at), None);
+
+        headers.insert("X-RateLimit-Remaining", "0".parse().unwrap());
+        headers.insert("X-RateLimit-Reset", "30".parse().unwrap());
+        headers.insert("X-RateLimit-Limit", "many".parse().unwrap());
+        let info = RateLimitInfo::from_headers(&headers, received_at).unwrap();
+        assert_eq!(info.limit, None);
+        assert_eq!(info.remaining, Some(0));
+        assert!(info.is_exhausted());
+        assert_eq!(info.reset_at().unwrap().timestamp(), 1_750_000_030);
+
+        headers.insert("X-RateLimit-Reset", "1750000600".parse().unwrap());
+        let info = RateLimitInfo::from_headers(&headers, received_at).unwrap();
+        assert_eq!(info.reset_at().unwrap().timestamp(), 1_750_000_600);
+    }
+}
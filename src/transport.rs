@@ -0,0 +1,103 @@
+//! Pluggable HTTP transport used to send API requests
+
+use crate::error::Result;
+use reqwest::{Client, Request, Response};
+use std::{fmt::Debug, future::Future, pin::Pin};
+
+#[cfg(feature = "test-util")]
+use std::{sync::Arc, time::Duration};
+
+/// Boxed future returned by the object-safe async traits in this crate
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Sends fully built HTTP requests on behalf of [`DocarooClient`](crate::DocarooClient)
+///
+/// The default transport is [`ReqwestTransport`]. Supply a custom implementation through
+/// [`DocarooConfig::transport`](crate::client::DocarooConfig) to intercept, record, or
+/// simulate traffic.
+pub trait Transport: Debug + Send + Sync {
+    /// Send a request and return the raw HTTP response
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<Response>>;
+}
+
+/// Default transport backed by a `reqwest::Client`
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl ReqwestTransport {
+    /// Create a transport that sends requests with the given HTTP client
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<Response>> {
+        Box::pin(async move { Ok(self.client.execute(request).await?) })
+    }
+}
+
+/// Transport that waits a fixed delay before delegating to another transport
+///
+/// Useful for exercising timeout and retry handling without a slow server.
+#[cfg(feature = "test-util")]
+#[derive(Debug, Clone)]
+pub struct DelayTransport {
+    /// Transport that actually sends the request
+    pub inner: Arc<dyn Transport>,
+    /// Time to wait before each request is sent
+    pub delay: Duration,
+}
+
+#[cfg(feature = "test-util")]
+impl Transport for DelayTransport {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<Response>> {
+        Box::pin(async move {
+            tokio::time::sleep(self.delay).await;
+            self.inner.send(request).await
+        })
+    }
+}
+
+#[cfg(all(test, feature = "test-util"))]
+mod tests {
+    use super::*;
+    use crate::{
+        DocarooClient, DocarooError,
+        client::DocarooConfig,
+        models::PricingRequest,
+    };
+    use wiremock::{Mock, MockServer, ResponseTemplate, matchers::method};
+
+    #[tokio::test]
+    async fn test_delay_transport_triggers_timeout() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let transport = DelayTransport {
+            inner: Arc::new(ReqwestTransport::new(Client::new())),
+            delay: Duration::from_secs(2),
+        };
+        let client = DocarooClient::with_config(
+            DocarooConfig::builder()
+                .api_key("test-key")
+                .base_url(server.uri())
+                .timeout(Duration::from_millis(100))
+                .transport(Arc::new(transport))
+                .build(),
+        );
+
+        let request = PricingRequest::builder()
+            .npis(vec!["1043566623".to_string()])
+            .condition_code("99214")
+            .build();
+
+        let error = client.pricing().get_in_network_rates(request).await.unwrap_err();
+        assert!(matches!(error, DocarooError::Timeout));
+    }
+}
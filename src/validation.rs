@@ -0,0 +1,347 @@
+//! Client-side validation shared by the API clients
+
+use crate::{
+    error::{DocarooError, Result},
+    models::CodeType,
+};
+
+/// Luhn digit sum contributed by the `80840` prefix that precedes every NPI
+const NPI_PREFIX_SUM: u32 = 24;
+
+/// Validate a National Provider Identifier
+///
+/// An NPI must be exactly 10 ASCII digits, the last of which is a Luhn check digit
+/// computed over the first nine digits prefixed with `80840` (the US health industry
+/// issuer prefix). Checking the digit catches most typos and transposed digits before
+/// a request is sent.
+///
+/// # Errors
+///
+/// Returns [`DocarooError::InvalidRequest`] whose message says whether the NPI has the
+/// wrong length, contains non-numeric characters, or fails the checksum.
+///
+/// # Example
+///
+/// ```
+/// use docaroo_rs::validation::validate_npi;
+///
+/// assert!(validate_npi("1234567893").is_ok());
+/// assert!(validate_npi("1234567890").is_err()); // wrong check digit
+/// ```
+pub fn validate_npi(npi: &str) -> Result<()> {
+    if npi.len() != 10 {
+        return Err(DocarooError::InvalidRequest(format!(
+            "Invalid NPI format: '{}'. NPIs must be 10-digit numbers (wrong length: {} characters)",
+            npi,
+            npi.chars().count()
+        )));
+    }
+
+    if !npi.chars().all(|c| c.is_ascii_digit()) {
+        return Err(DocarooError::InvalidRequest(format!(
+            "Invalid NPI format: '{}'. NPIs must be 10-digit numbers (non-numeric characters)",
+            npi
+        )));
+    }
+
+    let digits: Vec<u32> = npi.bytes().map(|b| u32::from(b - b'0')).collect();
+    if npi_check_digit(&digits[..9]) != digits[9] {
+        return Err(DocarooError::InvalidRequest(format!(
+            "Invalid NPI checksum: '{}' fails the Luhn check digit; check for typos",
+            npi
+        )));
+    }
+
+    Ok(())
+}
+
+/// Check whether `code` has the shape of a CPT code
+///
+/// CPT codes are five characters: four digits followed by a digit (Category I) or
+/// one of `F` (Category II), `T` (Category III) or `U` (proprietary laboratory
+/// analyses). This is a `const fn` so [`cpt!`](crate::cpt) can check literals at
+/// compile time; it does not check that the code exists.
+///
+/// # Example
+///
+/// ```
+/// use docaroo_rs::validation::is_cpt_code;
+///
+/// assert!(is_cpt_code("99214"));
+/// assert!(is_cpt_code("0001F"));
+/// assert!(!is_cpt_code("99"));
+/// ```
+pub const fn is_cpt_code(code: &str) -> bool {
+    let bytes = code.as_bytes();
+    if bytes.len() != 5 {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < 4 {
+        if !bytes[i].is_ascii_digit() {
+            return false;
+        }
+        i += 1;
+    }
+
+    matches!(bytes[4], b'0'..=b'9' | b'F' | b'T' | b'U')
+}
+
+/// Check that a billing code has the basic shape expected for its code type
+///
+/// Only the structure is checked, not whether the code exists. Letters must be
+/// uppercase. The shapes, as regular expressions:
+///
+/// | Code type | Shape | Example |
+/// |-----------|-------|---------|
+/// | CPT | `^\d{4}[0-9FTU]$` (see [`is_cpt_code`]) | `99214`, `0001F` |
+/// | HCPCS | `^[A-Z]\d{4}$` (Level II), or a CPT code (Level I) | `J3490` |
+/// | NDC | `^\d{10,11}$`, or dashed `4-4-2`, `5-3-2`, `5-4-1` or `5-4-2` | `0002-7510-01` |
+/// | ICD | `^[A-Z]\d[0-9A-Z](\.?[0-9A-Z]{1,4})?$` (ICD-10-CM) or `^[0-9A-Z]{7}$` (ICD-10-PCS) | `E11.9` |
+/// | MS-DRG | `^\d{3}$` | `470` |
+/// | RC | `^\d{3,4}$` | `0450` |
+/// | CDT | `^D\d{4}$` | `D0120` |
+/// | HIPPS | `^[0-9A-Z]{5}$` | `1AA11` |
+///
+/// Other code types have no fixed public format and only need to be non-empty.
+///
+/// # Errors
+///
+/// Returns [`DocarooError::InvalidRequest`] naming the code type and expected shape.
+///
+/// # Example
+///
+/// ```
+/// use docaroo_rs::{models::CodeType, validation::validate_code_format};
+///
+/// assert!(validate_code_format("J3490", CodeType::Hcpcs).is_ok());
+/// assert!(validate_code_format("J349", CodeType::Hcpcs).is_err());
+/// ```
+pub fn validate_code_format(code: &str, code_type: CodeType) -> Result<()> {
+    let bytes = code.as_bytes();
+    let digits = |range: &[u8]| !range.is_empty() && range.iter().all(u8::is_ascii_digit);
+    let alphanumeric = |range: &[u8]| {
+        range
+            .iter()
+            .all(|b| b.is_ascii_digit() || b.is_ascii_uppercase())
+    };
+
+    let (valid, expected) = match code_type {
+        CodeType::Cpt => (is_cpt_code(code), "four digits followed by a digit, F, T or U"),
+        CodeType::Hcpcs => (
+            is_cpt_code(code)
+                || (bytes.len() == 5 && bytes[0].is_ascii_uppercase() && digits(&bytes[1..])),
+            "an uppercase letter followed by four digits, or a CPT code",
+        ),
+        CodeType::Ndc => {
+            let segments: Vec<usize> = code.split('-').map(str::len).collect();
+            let valid = code.split('-').all(|segment| digits(segment.as_bytes()))
+                && matches!(
+                    segments.as_slice(),
+                    [10 | 11] | [4, 4, 2] | [5, 3, 2] | [5, 4, 1] | [5, 4, 2]
+                );
+            (valid, "10 or 11 digits, optionally dashed as 4-4-2, 5-3-2, 5-4-1 or 5-4-2")
+        }
+        CodeType::Icd => {
+            let cm = bytes.len() >= 3
+                && bytes[0].is_ascii_uppercase()
+                && bytes[1].is_ascii_digit()
+                && alphanumeric(&bytes[2..3])
+                && {
+                    let rest = bytes[3..].strip_prefix(b".").unwrap_or(&bytes[3..]);
+                    (rest.is_empty() && bytes.len() == 3)
+                        || ((1..=4).contains(&rest.len()) && alphanumeric(rest))
+                };
+            let pcs = bytes.len() == 7 && alphanumeric(bytes);
+            (cm || pcs, "an ICD-10-CM code such as E11.9, or a 7-character ICD-10-PCS code")
+        }
+        CodeType::MsDrg => (bytes.len() == 3 && digits(bytes), "three digits"),
+        CodeType::Rc => ((3..=4).contains(&bytes.len()) && digits(bytes), "three or four digits"),
+        CodeType::Cdt => (
+            bytes.len() == 5 && bytes[0] == b'D' && digits(&bytes[1..]),
+            "D followed by four digits",
+        ),
+        CodeType::Hipps => (bytes.len() == 5 && alphanumeric(bytes), "five letters or digits"),
+        _ => (!code.trim().is_empty(), "a non-empty code"),
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(DocarooError::InvalidRequest(format!(
+            "Invalid {} code '{}': expected {}",
+            code_type, code, expected
+        )))
+    }
+}
+
+/// Build a `(code, CodeType::Cpt)` pair from a CPT code literal checked at compile time
+///
+/// The literal must pass [`is_cpt_code`](crate::validation::is_cpt_code); anything
+/// else fails to compile, so typos in frequently used codes are caught early.
+///
+/// # Example
+///
+/// ```
+/// use docaroo_rs::{cpt, models::PricingRequest};
+///
+/// let (code, code_type) = cpt!("99214");
+/// let request = PricingRequest::builder()
+///     .npis(vec!["1043566623".to_string()])
+///     .condition_code(code)
+///     .code_type(code_type)
+///     .build();
+/// ```
+///
+/// ```compile_fail
+/// let (code, code_type) = docaroo_rs::cpt!("99");
+/// ```
+#[macro_export]
+macro_rules! cpt {
+    ($code:literal) => {{
+        const _: () = assert!(
+            $crate::validation::is_cpt_code($code),
+            concat!("invalid CPT code: ", $code)
+        );
+        ($code, $crate::models::CodeType::Cpt)
+    }};
+}
+
+/// Compute the Luhn check digit for the first nine digits of an NPI
+fn npi_check_digit(digits: &[u32]) -> u32 {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &digit)| {
+            // Double every other digit, starting with the rightmost
+            if i % 2 == 0 {
+                let doubled = digit * 2;
+                if doubled > 9 { doubled - 9 } else { doubled }
+            } else {
+                digit
+            }
+        })
+        .sum();
+
+    (10 - (sum + NPI_PREFIX_SUM) % 10) % 10
+}
+
+/// Build a checksum-valid NPI from a 9-digit base, for tests
+#[cfg(test)]
+pub(crate) fn npi_with_check_digit(base: u32) -> String {
+    let base = format!("{:09}", base);
+    let digits: Vec<u32> = base.bytes().map(|b| u32::from(b - b'0')).collect();
+    format!("{}{}", base, npi_check_digit(&digits))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_npis() {
+        for npi in ["1043566623", "1972767655", "1487648176", "1234567893", "2000000002"] {
+            assert!(validate_npi(npi).is_ok(), "{npi} should be valid");
+        }
+
+        for base in [100000000, 123456789, 987654321] {
+            assert!(validate_npi(&npi_with_check_digit(base)).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_invalid_npis() {
+        let message = validate_npi("123456789").unwrap_err().to_string();
+        assert!(message.contains("Invalid NPI format"));
+        assert!(message.contains("wrong length"));
+
+        let message = validate_npi("12345A7893").unwrap_err().to_string();
+        assert!(message.contains("Invalid NPI format"));
+        assert!(message.contains("non-numeric"));
+
+        // Wrong check digit, and a transposition of a valid NPI
+        for npi in ["1234567890", "1234576893"] {
+            let message = validate_npi(npi).unwrap_err().to_string();
+            assert!(message.contains("checksum"), "{npi}: {message}");
+        }
+    }
+
+    #[test]
+    fn test_validate_code_format() {
+        let valid = [
+            ("99214", CodeType::Cpt),
+            ("0001F", CodeType::Cpt),
+            ("0042T", CodeType::Cpt),
+            ("J3490", CodeType::Hcpcs),
+            ("G0008", CodeType::Hcpcs),
+            ("99214", CodeType::Hcpcs),
+            ("0002751001", CodeType::Ndc),
+            ("00002751001", CodeType::Ndc),
+            ("0002-7510-01", CodeType::Ndc),
+            ("50090-347-01", CodeType::Ndc),
+            ("00002-7510-1", CodeType::Ndc),
+            ("00002-7510-01", CodeType::Ndc),
+            ("E11", CodeType::Icd),
+            ("E11.9", CodeType::Icd),
+            ("S72.001A", CodeType::Icd),
+            ("0DTJ4ZZ", CodeType::Icd),
+            ("470", CodeType::MsDrg),
+            ("0450", CodeType::Rc),
+            ("D0120", CodeType::Cdt),
+            ("1AA11", CodeType::Hipps),
+            ("anything", CodeType::Local),
+        ];
+        for (code, code_type) in valid {
+            assert!(
+                validate_code_format(code, code_type).is_ok(),
+                "{code} should be a valid {code_type} code"
+            );
+        }
+
+        let invalid = [
+            ("9921", CodeType::Cpt),
+            ("99214X", CodeType::Cpt),
+            ("9921A", CodeType::Cpt),
+            ("J349", CodeType::Hcpcs),
+            ("j3490", CodeType::Hcpcs),
+            ("JJ490", CodeType::Hcpcs),
+            ("000275100", CodeType::Ndc),
+            ("000027510011", CodeType::Ndc),
+            ("0002-7510-1", CodeType::Ndc),
+            ("0002-75A0-01", CodeType::Ndc),
+            ("0002--751001", CodeType::Ndc),
+            ("E1", CodeType::Icd),
+            ("E11.", CodeType::Icd),
+            ("4700", CodeType::MsDrg),
+            ("X0120", CodeType::Cdt),
+            (" ", CodeType::Local),
+        ];
+        for (code, code_type) in invalid {
+            assert!(
+                validate_code_format(code, code_type).is_err(),
+                "{code} should not be a valid {code_type} code"
+            );
+        }
+
+        let message = validate_code_format("J349", CodeType::Hcpcs)
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("Invalid HCPCS code 'J349'"), "{message}");
+    }
+
+    #[test]
+    fn test_cpt_code_shape() {
+        for code in ["99214", "00100", "0001F", "0042T", "0001U"] {
+            assert!(is_cpt_code(code), "{code} should be a CPT code");
+        }
+        for code in ["99", "992145", "9921A", "A9214", "99214 ", ""] {
+            assert!(!is_cpt_code(code), "{code:?} should not be a CPT code");
+        }
+
+        let (code, code_type) = crate::cpt!("99214");
+        assert_eq!(code, "99214");
+        assert_eq!(code_type, crate::models::CodeType::Cpt);
+    }
+}
@@ -0,0 +1,15 @@
+//! Compile-time checks for the public API
+
+#[test]
+fn cpt_macro() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/cpt_valid.rs");
+    t.compile_fail("tests/ui/cpt_invalid.rs");
+}
+
+#[test]
+fn non_exhaustive_enums() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/non_exhaustive_match.rs");
+    t.compile_fail("tests/ui/non_exhaustive_no_wildcard.rs");
+}
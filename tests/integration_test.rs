@@ -48,12 +48,12 @@ fn test_likelihood_request_builder() {
     let request = LikelihoodRequest::builder()
         .npis(vec!["1111111111".to_string(), "2222222222".to_string()])
         .condition_code("90834")
-        .code_type("CPT")
+        .code_type(CodeType::Cpt)
         .build();
     
     assert_eq!(request.npis.len(), 2);
     assert_eq!(request.condition_code, "90834");
-    assert_eq!(request.code_type, "CPT");
+    assert_eq!(request.code_type, CodeType::Cpt);
 }
 
 #[test]
@@ -65,11 +65,13 @@ fn test_error_types() {
     
     // Test API error
     let error = DocarooError::ApiError {
+        status: 400,
         code: "bad_request".to_string(),
         message: "Invalid NPI".to_string(),
         request_id: Some("req_123".to_string()),
     };
     assert!(!error.is_retryable());
+    assert!(error.is_client_error());
     assert_eq!(error.request_id(), Some("req_123"));
     
     // Test authentication error
@@ -105,7 +107,7 @@ fn test_pricing_request_validation() {
     
     // Valid request should pass
     let valid_request = PricingRequest::builder()
-        .npis(vec![String::from("1234567890")])
+        .npis(vec![String::from("1234567893")])
         .condition_code("99214")
         .build();
     
@@ -122,14 +124,14 @@ fn test_likelihood_request_validation() {
     
     // Valid request should be built correctly
     let valid_request = LikelihoodRequest::builder()
-        .npis(vec![String::from("1234567890")])
+        .npis(vec![String::from("1234567893")])
         .condition_code("99214")
-        .code_type("CPT")
+        .code_type(CodeType::Cpt)
         .build();
     
     assert!(!valid_request.npis.is_empty());
     assert!(!valid_request.condition_code.is_empty());
-    assert!(!valid_request.code_type.is_empty());
+    assert_eq!(valid_request.code_type, CodeType::Cpt);
 }
 
 #[cfg(test)]
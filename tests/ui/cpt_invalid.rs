@@ -0,0 +1,5 @@
+use docaroo_rs::cpt;
+
+fn main() {
+    let _ = cpt!("99");
+}
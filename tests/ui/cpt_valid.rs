@@ -0,0 +1,13 @@
+use docaroo_rs::{cpt, models::{CodeType, PricingRequest}};
+
+fn main() {
+    let (code, code_type) = cpt!("99214");
+    assert_eq!(code_type, CodeType::Cpt);
+
+    let request = PricingRequest::builder()
+        .npis(vec!["1043566623".to_string()])
+        .condition_code(code)
+        .code_type(code_type)
+        .build();
+    assert_eq!(request.condition_code, "99214");
+}
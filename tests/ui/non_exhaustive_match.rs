@@ -0,0 +1,23 @@
+use docaroo_rs::{error::DocarooError, models::CodeType};
+
+fn describe(error: &DocarooError) -> &'static str {
+    match error {
+        DocarooError::RateLimitExceeded { .. } => "rate limited",
+        DocarooError::AuthenticationFailed(_) => "bad API key",
+        _ => "other",
+    }
+}
+
+fn is_drg(code_type: CodeType) -> bool {
+    match code_type {
+        CodeType::MsDrg | CodeType::RDrg | CodeType::SDrg => true,
+        CodeType::Cpt | CodeType::Hcpcs => false,
+        _ => code_type.as_str().ends_with("DRG"),
+    }
+}
+
+fn main() {
+    assert_eq!(describe(&DocarooError::Timeout), "other");
+    assert!(is_drg(CodeType::ApDrg));
+    assert!(!is_drg(CodeType::Ndc));
+}
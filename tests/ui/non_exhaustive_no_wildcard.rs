@@ -0,0 +1,24 @@
+use docaroo_rs::models::CodeType;
+
+fn main() {
+    // Every current variant is listed, but new ones may be added
+    let _ = match CodeType::Cpt {
+        CodeType::Cpt
+        | CodeType::Ndc
+        | CodeType::Hcpcs
+        | CodeType::Rc
+        | CodeType::Icd
+        | CodeType::MsDrg
+        | CodeType::RDrg
+        | CodeType::SDrg
+        | CodeType::ApsDrg
+        | CodeType::ApDrg
+        | CodeType::AprDrg
+        | CodeType::Apc
+        | CodeType::Local
+        | CodeType::Eapg
+        | CodeType::Hipps
+        | CodeType::Cdt
+        | CodeType::CstmAll => 1,
+    };
+}